@@ -0,0 +1,126 @@
+#import bevy_pbr::mesh_view_bindings
+#import bevy_pbr::mesh_bindings
+#import bevy_pbr::mesh_functions
+
+#import bevy_pbr::pbr_types
+#import bevy_pbr::utils
+#import bevy_pbr::clustered_forward
+#import bevy_pbr::lighting
+#import bevy_pbr::shadows
+#import bevy_pbr::fog
+#import bevy_pbr::pbr_functions
+
+#ifdef TONEMAP_IN_SHADER
+#import bevy_core_pipeline::tonemapping
+#endif
+
+struct OceanMaterial {
+    color: vec4<f32>,
+    foam_color: vec4<f32>,
+    waves: array<vec4<f32>, 4>,
+};
+
+@group(1) @binding(0)
+var<uniform> material: OceanMaterial;
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec4<f32>,
+    @location(1) world_normal: vec3<f32>,
+    @location(2) crest: f32,
+};
+
+// has to match OceanWaves::get_displacement, the waves are sampled on the cpu for physics
+fn gerstner(
+    wave: vec4<f32>,
+    position: vec2<f32>,
+    tangent: ptr<function, vec3<f32>>,
+    binormal: ptr<function, vec3<f32>>,
+) -> vec3<f32> {
+    let steepness = wave.z;
+    let k = 2.0 * PI / wave.w;
+    let c = sqrt(9.81 / k);
+    let direction = normalize(wave.xy);
+    let f = k * (dot(direction, position) - c * globals.time);
+    let a = steepness / k;
+
+    *tangent += vec3<f32>(
+        -direction.x * direction.x * steepness * sin(f),
+        direction.x * steepness * cos(f),
+        -direction.x * direction.y * steepness * sin(f),
+    );
+    *binormal += vec3<f32>(
+        -direction.x * direction.y * steepness * sin(f),
+        direction.y * steepness * cos(f),
+        -direction.y * direction.y * steepness * sin(f),
+    );
+
+    return vec3<f32>(direction.x * a * cos(f), a * sin(f), direction.y * a * cos(f));
+}
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+    let world_position = mesh_position_local_to_world(mesh.model, vec4<f32>(vertex.position, 1.0));
+
+    var tangent = vec3<f32>(1.0, 0.0, 0.0);
+    var binormal = vec3<f32>(0.0, 0.0, 1.0);
+    var offset = vec3<f32>(0.0);
+    var amplitude = 0.0;
+
+    for (var i = 0; i < 4; i += 1) {
+        let wave = material.waves[i];
+        if wave.z > 0.0 {
+            offset += gerstner(wave, world_position.xz, &tangent, &binormal);
+            amplitude += wave.z * wave.w / (2.0 * PI);
+        }
+    }
+
+    out.world_position = vec4<f32>(world_position.xyz + offset, 1.0);
+    out.clip_position = mesh_position_world_to_clip(out.world_position);
+    out.world_normal = normalize(cross(binormal, tangent));
+    out.crest = offset.y / max(amplitude, 0.0001);
+
+    return out;
+}
+
+@fragment
+fn fragment(
+    @builtin(front_facing) is_front: bool,
+    in: VertexOutput,
+) -> @location(0) vec4<f32> {
+    var normal = normalize(in.world_normal);
+    if !is_front {
+        normal = -normal;
+    }
+
+    let foam = smoothstep(0.5, 0.9, in.crest);
+    let color = mix(material.color, material.foam_color, foam);
+
+    var pbr_input: PbrInput = pbr_input_new();
+    pbr_input.material.base_color = color;
+    pbr_input.material.perceptual_roughness = mix(0.1, 0.8, foam);
+    pbr_input.material.reflectance = 0.2;
+    pbr_input.frag_coord = in.clip_position;
+    pbr_input.world_position = in.world_position;
+    pbr_input.world_normal = normal;
+    pbr_input.is_orthographic = view.projection[3].w == 1.0;
+    pbr_input.N = normal;
+    pbr_input.V = calculate_view(in.world_position, pbr_input.is_orthographic);
+
+    var output_color = pbr(pbr_input);
+
+    if fog.mode != FOG_MODE_OFF {
+        output_color = apply_fog(output_color, in.world_position.xyz, view.world_position.xyz);
+    }
+
+#ifdef TONEMAP_IN_SHADER
+    output_color = tone_mapping(output_color);
+#endif
+
+    return output_color;
+}
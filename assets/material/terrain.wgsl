@@ -0,0 +1,99 @@
+#import bevy_pbr::mesh_view_bindings
+#import bevy_pbr::mesh_bindings
+#import bevy_pbr::mesh_functions
+
+#import bevy_pbr::pbr_types
+#import bevy_pbr::utils
+#import bevy_pbr::clustered_forward
+#import bevy_pbr::lighting
+#import bevy_pbr::shadows
+#import bevy_pbr::fog
+#import bevy_pbr::pbr_functions
+
+#ifdef TONEMAP_IN_SHADER
+#import bevy_core_pipeline::tonemapping
+#endif
+
+#import deep::simplex_noise_3d
+
+struct TerrainMaterial {
+    cliff_color: vec4<f32>,
+    cliff_start: f32,
+    cliff_end: f32,
+    depth_min: f32,
+    depth_max: f32,
+    depth_shade: f32,
+    noise_scale: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> material: TerrainMaterial;
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) color: vec4<f32>,
+    @location(3) biome_weights: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec4<f32>,
+    @location(1) world_normal: vec3<f32>,
+    @location(2) color: vec4<f32>,
+    @location(3) biome_weights: vec4<f32>,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+    out.world_position = mesh_position_local_to_world(mesh.model, vec4<f32>(vertex.position, 1.0));
+    out.clip_position = mesh_position_world_to_clip(out.world_position);
+    out.world_normal = mesh_normal_local_to_world(vertex.normal);
+    out.color = vertex.color;
+    out.biome_weights = vertex.biome_weights;
+
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let normal = normalize(in.world_normal);
+    let noise = simplex(in.world_position.xyz * material.noise_scale);
+
+    // cliffs replace the vertex colors, the noise keeps their border from following the mesh
+    let slope = 1.0 - normal.y + noise * 0.05;
+    let cliff = smoothstep(material.cliff_start, material.cliff_end, slope);
+
+    var color = mix(in.color, material.cliff_color, cliff);
+
+    // strength of the noise per biome: sand flats, rocky slopes, vent fields and trenches
+    let weights = in.biome_weights / max(dot(in.biome_weights, vec4<f32>(1.0)), 0.0001);
+    let variation = dot(weights, vec4<f32>(0.1, 0.25, 0.3, 0.05));
+
+    let depth = smoothstep(material.depth_min, material.depth_max, in.world_position.y);
+    let shade = mix(material.depth_shade, 1.0, depth) * (1.0 + noise * variation);
+    color = vec4<f32>(color.rgb * shade, 1.0);
+
+    var pbr_input: PbrInput = pbr_input_new();
+    pbr_input.material.base_color = color;
+    pbr_input.material.perceptual_roughness = 0.9;
+    pbr_input.frag_coord = in.clip_position;
+    pbr_input.world_position = in.world_position;
+    pbr_input.world_normal = normal;
+    pbr_input.is_orthographic = view.projection[3].w == 1.0;
+    pbr_input.N = normal;
+    pbr_input.V = calculate_view(in.world_position, pbr_input.is_orthographic);
+
+    var output_color = pbr(pbr_input);
+
+    if fog.mode != FOG_MODE_OFF {
+        output_color = apply_fog(output_color, in.world_position.xyz, view.world_position.xyz);
+    }
+
+#ifdef TONEMAP_IN_SHADER
+    output_color = tone_mapping(output_color);
+#endif
+
+    return output_color;
+}
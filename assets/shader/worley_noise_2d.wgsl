@@ -0,0 +1,62 @@
+// smaller jitter gives more regular pattern
+
+fn noise_permute_2d_vec3f(x: vec3<f32>) -> vec3<f32> {
+    return (((x * 34.0) + 10.0) * x) % 289.0;
+}
+
+fn worley_2d(v: vec2<f32>, jitter: f32) -> vec2<f32> {
+    let k = 0.142857142857;     // 1/7
+    let ko = 0.428571428571;    // 3/7
+
+    let pi = floor(v) % 289.0;
+    let pf = fract(v);
+
+    let offset_i = vec3(-1.0, 0.0, 1.0);
+    let offset_f = vec3(-0.5, 0.5, 1.5);
+
+    let px = noise_permute_2d_vec3f(pi.x + offset_i);
+
+    var p = noise_permute_2d_vec3f(px.x + pi.y + offset_i);
+    var ox = fract(p * k) - ko;
+    var oy = (floor(p * k) % 7.0) * k - ko;
+    var dx = pf.x + 0.5 + jitter * ox;
+    var dy = pf.y - offset_f + jitter * oy;
+    var d1 = dx * dx + dy * dy;
+
+    p = noise_permute_2d_vec3f(px.y + pi.y + offset_i);
+    ox = fract(p * k) - ko;
+    oy = (floor(p * k) % 7.0) * k - ko;
+    dx = pf.x - 0.5 + jitter * ox;
+    dy = pf.y - offset_f + jitter * oy;
+    var d2 = dx * dx + dy * dy;
+
+    p = noise_permute_2d_vec3f(px.z + pi.y + offset_i);
+    ox = fract(p * k) - ko;
+    oy = (floor(p * k) % 7.0) * k - ko;
+    dx = pf.x - 1.5 + jitter * ox;
+    dy = pf.y - offset_f + jitter * oy;
+    let d3 = dx * dx + dy * dy;
+
+    // Sort out the two smallest distances (F1, F2)
+    let d1a = min(d1, d2);
+    d2 = max(d1, d2);           // Swap to keep candidates for F2
+    d2 = min(d2, d3);           // neither F1 nor F2 are now in d3
+    d1 = min(d1a, d2);          // F1 is now in d1
+    d2 = max(d1a, d2);          // Swap to keep candidates for F2
+    if d1.x > d1.y {
+        let tmp = d1.x;
+        d1.x = d1.y;
+        d1.y = tmp;
+    }
+    if d1.x > d1.z {            // F1 is in d1.x
+        let tmp = d1.x;
+        d1.x = d1.z;
+        d1.z = tmp;
+    }
+    d1.y = min(d1.y, d2.y);     // F2 is now not in d2.yz
+    d1.z = min(d1.z, d2.z);
+    d1.y = min(d1.y, d1.z);     // nor in d1.z
+    d1.y = min(d1.y, d2.x);     // F2 is in d1.y, we're done.
+
+    return sqrt(d1.xy);         // F1, F2
+}
@@ -0,0 +1,129 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use nalgebra::Vector2;
+
+use crate::HeightMap;
+
+type Vector2f32 = Vector2<f32>;
+
+/// Iso line of the height map in pixel coordinates. Open contours start and end at the border
+/// of the height map.
+#[derive(Debug, Clone)]
+pub struct Contour {
+    pub points: Vec<Vector2f32>,
+    pub closed: bool,
+}
+
+/// Extracts the iso lines at `level` between 0.0 and 1.0 with marching squares. Pixels with a
+/// height of at least `level` count as inside.
+pub fn generate(height_map: &HeightMap, level: f32) -> Vec<Contour> {
+    let width = height_map.width() as usize;
+    let height = height_map.height() as usize;
+    if width < 2 || height < 2 {
+        return Vec::new();
+    }
+
+    let get_height =
+        |x: usize, y: usize| height_map.get_pixel(x as u32, y as u32).0[0] as f32 / u16::MAX as f32;
+
+    // INFO: every crossed grid edge gets its interpolated point and the edges it is linked to
+    let mut points = BTreeMap::<usize, Vector2f32>::new();
+    let mut links = BTreeMap::<usize, Vec<usize>>::new();
+
+    for y in 0..height - 1 {
+        for x in 0..width - 1 {
+            let corners = [(x, y), (x + 1, y), (x + 1, y + 1), (x, y + 1)];
+            let heights = corners.map(|(x, y)| get_height(x, y));
+            let inside = heights.map(|value| value >= level);
+
+            // INFO: bottom, right, top and left edge as corner pairs
+            let edges = [(0, 1), (1, 2), (3, 2), (0, 3)];
+            let crossed: Vec<usize> = (0..4)
+                .filter(|edge| {
+                    let (a, b) = edges[*edge];
+                    inside[a] != inside[b]
+                })
+                .collect();
+
+            if crossed.is_empty() {
+                continue;
+            }
+
+            let keys = [
+                get_edge_key(width, x, y, false),
+                get_edge_key(width, x + 1, y, true),
+                get_edge_key(width, x, y + 1, false),
+                get_edge_key(width, x, y, true),
+            ];
+
+            for edge in crossed.iter() {
+                let (a, b) = edges[*edge];
+                points.entry(keys[*edge]).or_insert_with(|| {
+                    let t = (level - heights[a]) / (heights[b] - heights[a]);
+                    let start = Vector2f32::new(corners[a].0 as f32, corners[a].1 as f32);
+                    let end = Vector2f32::new(corners[b].0 as f32, corners[b].1 as f32);
+
+                    start.lerp(&end, t)
+                });
+            }
+
+            let segments = if crossed.len() == 2 {
+                vec![(crossed[0], crossed[1])]
+            } else {
+                // INFO: saddle, the average of the corners decides which corners are cut off
+                let center = heights.iter().sum::<f32>() / 4.0 >= level;
+                if inside[0] != center {
+                    vec![(3, 0), (1, 2)]
+                } else {
+                    vec![(0, 1), (2, 3)]
+                }
+            };
+
+            for (a, b) in segments {
+                links.entry(keys[a]).or_default().push(keys[b]);
+                links.entry(keys[b]).or_default().push(keys[a]);
+            }
+        }
+    }
+
+    let mut contours = Vec::new();
+    let mut visited = BTreeSet::<usize>::new();
+
+    // INFO: open contours first, so they are walked from one of their ends
+    let open_starts: Vec<usize> = links
+        .iter()
+        .filter(|(_, linked)| linked.len() == 1)
+        .map(|(key, _)| *key)
+        .collect();
+
+    for start in open_starts.into_iter().chain(links.keys().copied()) {
+        if visited.contains(&start) {
+            continue;
+        }
+
+        let mut contour_points = Vec::new();
+        let mut current = start;
+
+        let closed = loop {
+            visited.insert(current);
+            contour_points.push(points[&current]);
+
+            let linked = &links[&current];
+            match linked.iter().find(|key| !visited.contains(key)) {
+                Some(next) => current = *next,
+                None => break linked.len() == 2 && linked.contains(&start) && current != start,
+            }
+        };
+
+        contours.push(Contour {
+            points: contour_points,
+            closed,
+        });
+    }
+
+    contours
+}
+
+fn get_edge_key(width: usize, x: usize, y: usize, vertical: bool) -> usize {
+    (y * width + x) * 2 + vertical as usize
+}
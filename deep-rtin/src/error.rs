@@ -1,10 +1,11 @@
 use crate::{
     heightmap::{self, HeightMap},
+    mask::{Mask, MaskCoverage},
     rtin::{self, Triangle, Vector2u32},
     u32_extensions::log_2,
 };
 
-pub(crate) fn generate(height_map: &HeightMap) -> Vec<f32> {
+pub(crate) fn generate(height_map: &HeightMap, mask: Option<&Mask>) -> Vec<f32> {
     let side_length = height_map.width();
     let grid_size = side_length + 1;
     let triangle_count = side_length * side_length * 2 - 2;
@@ -15,16 +16,25 @@ pub(crate) fn generate(height_map: &HeightMap) -> Vec<f32> {
     let level_count = log_2(side_length) * 2;
     let last_level_index = rtin::get_first_triangle_index(level_count - 1);
 
+    let coverage = mask.map(MaskCoverage::new);
+
     for triangle_index in (0..triangle_count).rev() {
         let id = triangle_index + 2;
 
         let (triangle, midpoint_vector) = rtin::get_triangle_and_midpoint_vector(id, grid_size);
 
-        let triangle_error = get_triangle_error(height_map, triangle, midpoint_vector);
+        // INFO: triangles crossing the mask boundary are always split, the error propagation
+        // splits their neighbours as well, so the mesh stays free of cracks
+        let triangle_error = match &coverage {
+            Some(coverage) if coverage.is_partial(triangle) => f32::INFINITY,
+            _ => get_triangle_error(height_map, triangle, midpoint_vector),
+        };
         let error_vector_index = get_index(grid_size, midpoint_vector);
 
         if triangle_index >= last_level_index {
-            errors[error_vector_index] = triangle_error;
+            // INFO: both neighbours of a hypotenuse share the midpoint, a forced split of one of
+            // them must not be overwritten
+            errors[error_vector_index] = triangle_error.max(errors[error_vector_index]);
         } else {
             let left_child_triangle_index = rtin::get_left_child_triangle_index(id);
             let (_, left_child_midpoint_vector) =
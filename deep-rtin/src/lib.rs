@@ -1,12 +1,16 @@
 use std::collections::HashMap;
 
-use heightmap::HeightMap;
+pub use contour::Contour;
+pub use heightmap::HeightMap;
+pub use mask::Mask;
 use nalgebra::Vector3;
 use rtin::{Triangle, Vector2u32};
 use u32_extensions::subtract_abs;
 
+mod contour;
 mod error;
 mod heightmap;
+mod mask;
 mod rtin;
 mod u32_extensions;
 
@@ -36,8 +40,23 @@ fn is_power_of_2(x: u32) -> bool {
     (x & !(x & (x - 1))) > 0
 }
 
+/// Returns the iso lines of the height map at `level` between 0.0 and 1.0.
+pub fn get_contours(height_map: &HeightMap, level: f32) -> Vec<Contour> {
+    contour::generate(height_map, level)
+}
+
 pub fn get_errors(height_map: &HeightMap) -> Vec<f32> {
-    error::generate(height_map)
+    error::generate(height_map, None)
+}
+
+/// Like `get_errors`, but triangles crossing the boundary of the masked cells are always split.
+pub fn get_masked_errors(height_map: &HeightMap, mask: &Mask) -> Vec<f32> {
+    assert!(
+        mask.dimensions() == height_map.dimensions(),
+        "Mask must have the size of the HeightMap!"
+    );
+
+    error::generate(height_map, Some(mask))
 }
 
 pub fn get_triangles(error_threshold: f32, errors: &[f32]) -> Vec<Triangle> {
@@ -63,6 +82,15 @@ pub fn get_triangles(error_threshold: f32, errors: &[f32]) -> Vec<Triangle> {
     triangles
 }
 
+/// Removes the triangles on masked cells, the triangles have to be generated from
+/// `get_masked_errors` with the same mask.
+pub fn remove_masked_triangles(triangles: Vec<Triangle>, mask: &Mask) -> Vec<Triangle> {
+    triangles
+        .into_iter()
+        .filter(|triangle| !mask::is_masked(mask, *triangle))
+        .collect()
+}
+
 fn populate_triangle_ids(
     error_threshold: f32,
     errors: &[f32],
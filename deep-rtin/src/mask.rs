@@ -0,0 +1,105 @@
+use image::{ImageBuffer, Luma};
+
+use crate::rtin::{Triangle, Vector2u32};
+
+/// Marks the cells of a height map with the same size, cell `(x, y)` spans the vertices
+/// `(x, y)` to `(x + 1, y + 1)`. Cells with a value above zero are cut out of the mesh.
+pub type Mask = ImageBuffer<Luma<u8>, Vec<u8>>;
+
+/// Summed area table of the masked cells, so triangles far from the mask boundary are
+/// classified without visiting their cells.
+pub(crate) struct MaskCoverage<'a> {
+    mask: &'a Mask,
+    sums: Vec<u32>,
+}
+
+impl<'a> MaskCoverage<'a> {
+    pub(crate) fn new(mask: &'a Mask) -> Self {
+        let width = mask.width() as usize + 1;
+        let mut sums = vec![0; width * (mask.height() as usize + 1)];
+
+        for (x, y, pixel) in mask.enumerate_pixels() {
+            let (x, y) = (x as usize, y as usize);
+            sums[(y + 1) * width + x + 1] = (pixel.0[0] > 0) as u32 + sums[y * width + x + 1]
+                - sums[y * width + x]
+                + sums[(y + 1) * width + x];
+        }
+
+        Self { mask, sums }
+    }
+
+    /// Whether the triangle covers masked and unmasked cells at once and has to be split.
+    pub(crate) fn is_partial(&self, triangle: Triangle) -> bool {
+        let vertices = [triangle.1, triangle.2, triangle.3];
+        let min = vertices[0].inf(&vertices[1]).inf(&vertices[2]);
+        let max = vertices[0]
+            .sup(&vertices[1])
+            .sup(&vertices[2])
+            .inf(&Vector2u32::new(self.mask.width(), self.mask.height()));
+
+        let masked = self.get_count(min, max);
+        if masked == 0 || masked == (max[0] - min[0]) * (max[1] - min[1]) {
+            return false;
+        }
+
+        let mut has_masked = false;
+        let mut has_unmasked = false;
+
+        for y in min[1]..max[1] {
+            for x in min[0]..max[0] {
+                if !contains_cell(&vertices, x, y) {
+                    continue;
+                }
+
+                if self.mask.get_pixel(x, y).0[0] > 0 {
+                    has_masked = true;
+                } else {
+                    has_unmasked = true;
+                }
+
+                if has_masked && has_unmasked {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    fn get_count(&self, min: Vector2u32, max: Vector2u32) -> u32 {
+        let width = self.mask.width() as usize + 1;
+        let get_sum = |x: u32, y: u32| self.sums[y as usize * width + x as usize];
+
+        get_sum(max[0], max[1]) + get_sum(min[0], min[1])
+            - get_sum(min[0], max[1])
+            - get_sum(max[0], min[1])
+    }
+}
+
+/// Whether the triangle lies on masked cells, only valid for triangles which are not partial.
+pub(crate) fn is_masked(mask: &Mask, triangle: Triangle) -> bool {
+    let centroid = (triangle.1 + triangle.2 + triangle.3) / 3;
+    let x = centroid[0].min(mask.width() - 1);
+    let y = centroid[1].min(mask.height() - 1);
+
+    mask.get_pixel(x, y).0[0] > 0
+}
+
+// INFO: the triangle edges are axis aligned or diagonal, so a cell either lies on one side of
+// an edge or is split in half with its center on the edge
+fn contains_cell(vertices: &[Vector2u32; 3], x: u32, y: u32) -> bool {
+    let doubled = vertices.map(|vertex| (vertex[0] as i64 * 2, vertex[1] as i64 * 2));
+    let center = (x as i64 * 2 + 1, y as i64 * 2 + 1);
+
+    let get_side = |a: (i64, i64), b: (i64, i64)| {
+        (b.0 - a.0) * (center.1 - a.1) - (b.1 - a.1) * (center.0 - a.0)
+    };
+
+    let sides = [
+        get_side(doubled[0], doubled[1]),
+        get_side(doubled[1], doubled[2]),
+        get_side(doubled[2], doubled[0]),
+    ];
+
+    sides.iter().all(|side| *side >= 0) || sides.iter().all(|side| *side <= 0)
+}
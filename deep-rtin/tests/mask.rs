@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+
+use deep_rtin::{HeightMap, Mask};
+use image::Luma;
+
+const SIZE: u32 = 64;
+
+type Vertex = (i64, i64);
+
+fn get_height_map() -> HeightMap {
+    HeightMap::from_fn(SIZE, SIZE, |x, y| {
+        let height = ((x as f32 * 0.15).sin() * (y as f32 * 0.1).cos() + 1.0) / 2.0;
+        Luma([(height * u16::MAX as f32) as u16])
+    })
+}
+
+fn get_masks() -> Vec<Mask> {
+    let rectangle = Mask::from_fn(SIZE, SIZE, |x, y| {
+        Luma([((11..30).contains(&x) && (7..22).contains(&y)) as u8 * 255])
+    });
+    let circle = Mask::from_fn(SIZE, SIZE, |x, y| {
+        let (dx, dy) = (x as f32 + 0.5 - 40.0, y as f32 + 0.5 - 37.0);
+        Luma([(dx * dx + dy * dy < 13.0 * 13.0) as u8 * 255])
+    });
+    let cell = Mask::from_fn(SIZE, SIZE, |x, y| Luma([(x == 33 && y == 17) as u8 * 255]));
+    let border = Mask::from_fn(SIZE, SIZE, |x, _| Luma([(x >= SIZE - 5) as u8 * 255]));
+
+    vec![rectangle, circle, cell, border]
+}
+
+fn get_masked_mesh(mask: &Mask) -> Vec<[Vertex; 3]> {
+    let errors = deep_rtin::get_masked_errors(&get_height_map(), mask);
+    let triangles = deep_rtin::get_triangles(0.05, &errors);
+
+    deep_rtin::remove_masked_triangles(triangles, mask)
+        .into_iter()
+        .map(|(_, a, b, c)| [a, b, c].map(|vertex| (vertex[0] as i64, vertex[1] as i64)))
+        .collect()
+}
+
+fn get_doubled_area(vertices: &[Vertex; 3]) -> i64 {
+    let [a, b, c] = vertices;
+    ((b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)).abs()
+}
+
+/// Cells whose center lies inside or on the border of the triangle.
+fn get_cells(vertices: &[Vertex; 3]) -> Vec<(u32, u32)> {
+    let min = vertices.iter().fold((i64::MAX, i64::MAX), |min, v| {
+        (min.0.min(v.0), min.1.min(v.1))
+    });
+    let max = vertices.iter().fold((i64::MIN, i64::MIN), |max, v| {
+        (max.0.max(v.0), max.1.max(v.1))
+    });
+
+    let doubled = vertices.map(|vertex| (vertex.0 * 2, vertex.1 * 2));
+    let mut cells = Vec::new();
+
+    for y in min.1..max.1 {
+        for x in min.0..max.0 {
+            let center = (x * 2 + 1, y * 2 + 1);
+            let sides: Vec<i64> = (0..3)
+                .map(|index| {
+                    let (a, b) = (doubled[index], doubled[(index + 1) % 3]);
+                    (b.0 - a.0) * (center.1 - a.1) - (b.1 - a.1) * (center.0 - a.0)
+                })
+                .collect();
+
+            if sides.iter().all(|side| *side >= 0) || sides.iter().all(|side| *side <= 0) {
+                cells.push((x as u32, y as u32));
+            }
+        }
+    }
+
+    cells
+}
+
+#[test]
+fn masked_cells_are_cut_out() {
+    for mask in get_masks() {
+        let mesh = get_masked_mesh(&mask);
+
+        for vertices in mesh.iter() {
+            let cells = get_cells(vertices);
+            assert!(!cells.is_empty());
+            assert!(
+                cells.iter().all(|(x, y)| mask.get_pixel(*x, *y).0[0] == 0),
+                "triangle {:?} covers masked cells",
+                vertices
+            );
+        }
+
+        // INFO: the remaining triangles still cover every unmasked cell exactly once
+        let unmasked = mask.pixels().filter(|pixel| pixel.0[0] == 0).count() as i64;
+        let area: i64 = mesh.iter().map(get_doubled_area).sum();
+        assert_eq!(area, unmasked * 2);
+    }
+}
+
+#[test]
+fn masked_meshes_have_no_t_junctions() {
+    for mask in get_masks() {
+        let mesh = get_masked_mesh(&mask);
+        let vertices: HashSet<Vertex> = mesh.iter().flatten().copied().collect();
+
+        for triangle in mesh.iter() {
+            for index in 0..3 {
+                let (start, end) = (triangle[index], triangle[(index + 1) % 3]);
+
+                // INFO: edges are axis aligned or diagonal, so every grid point on them is a step
+                let length = (end.0 - start.0).abs().max((end.1 - start.1).abs());
+                let step = ((end.0 - start.0) / length, (end.1 - start.1) / length);
+
+                for position in 1..length {
+                    let point = (start.0 + step.0 * position, start.1 + step.1 * position);
+                    assert!(
+                        !vertices.contains(&point),
+                        "vertex {:?} lies on the edge {:?} to {:?}",
+                        point,
+                        start,
+                        end
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::Vector2f64;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bounds {
+    pub min: Vector2f64,
+    pub max: Vector2f64,
+}
+
+impl Bounds {
+    pub fn new(min: Vector2f64, max: Vector2f64) -> Self {
+        assert!(
+            min.x < max.x && min.y < max.y,
+            "Bounds min must be smaller than max!"
+        );
+
+        Self { min, max }
+    }
+
+    pub fn width(&self) -> f64 {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> f64 {
+        self.max.y - self.min.y
+    }
+
+    pub fn contains(&self, point: Vector2f64) -> bool {
+        point.x >= self.min.x
+            && point.x < self.max.x
+            && point.y >= self.min.y
+            && point.y < self.max.y
+    }
+
+    pub fn wrap(&self, point: Vector2f64) -> Vector2f64 {
+        Vector2f64::new(
+            self.min.x + (point.x - self.min.x).rem_euclid(self.width()),
+            self.min.y + (point.y - self.min.y).rem_euclid(self.height()),
+        )
+    }
+}
@@ -0,0 +1,616 @@
+use std::collections::HashMap;
+
+use crate::{predicates, Vector2f64};
+
+// INFO: scales the bounding box of all points to the size of the super triangle
+const SUPER_TRIANGLE_SCALE: f64 = 100.0;
+
+// INFO: the super triangle uses the last three indices, so points can be appended freely
+const SUPER_VERTEX: usize = usize::MAX - 2;
+
+pub(crate) type Triangle = [usize; 3];
+
+/// Delaunay triangulation built with Bowyer-Watson, every triangle is stored counterclockwise.
+/// Vertices from `SUPER_VERTEX` onwards form the super triangle.
+pub(crate) struct Triangulation {
+    pub(crate) vertices: Vec<Vector2f64>,
+    pub(crate) inserted: Vec<bool>,
+    super_vertices: [Vector2f64; 3],
+    triangles: Vec<Option<Triangle>>,
+    free_triangles: Vec<usize>,
+    // INFO: directed edge to the triangle containing it in counterclockwise order
+    edges: HashMap<(usize, usize), usize>,
+    // INFO: any triangle touching the point, kept valid for inserted points
+    point_triangles: Vec<usize>,
+    last_triangle: usize,
+}
+
+impl Triangulation {
+    pub(crate) fn new(points: &[Vector2f64]) -> Self {
+        let (min, max) = get_bounding_box(points);
+        let mut triangulation = Self::with_bounding_box(min, max);
+
+        triangulation.vertices = points.to_vec();
+        triangulation.inserted = vec![false; points.len()];
+        triangulation.point_triangles = vec![0; points.len()];
+
+        for index in get_insertion_order(points) {
+            triangulation.inserted[index] = triangulation.insert(index).is_some();
+        }
+
+        triangulation
+    }
+
+    /// Creates an empty triangulation whose super triangle encloses the bounding box, points
+    /// outside of it may break the triangulation.
+    pub(crate) fn with_bounding_box(min: Vector2f64, max: Vector2f64) -> Self {
+        let mut triangulation = Self {
+            vertices: vec![],
+            inserted: vec![],
+            super_vertices: generate_super_triangle(min, max),
+            triangles: vec![],
+            free_triangles: vec![],
+            edges: HashMap::new(),
+            point_triangles: vec![],
+            last_triangle: 0,
+        };
+
+        triangulation.add_triangle([SUPER_VERTEX, SUPER_VERTEX + 1, SUPER_VERTEX + 2]);
+        triangulation
+    }
+
+    pub(crate) fn point_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub(crate) fn is_super_vertex(&self, index: usize) -> bool {
+        index >= SUPER_VERTEX
+    }
+
+    pub(crate) fn get_vertex(&self, index: usize) -> Vector2f64 {
+        if self.is_super_vertex(index) {
+            self.super_vertices[index - SUPER_VERTEX]
+        } else {
+            self.vertices[index]
+        }
+    }
+
+    /// Appends and inserts a point. Returns its index with the points whose Delaunay neighbours
+    /// changed, or None if the point duplicates an inserted one.
+    pub(crate) fn push(&mut self, point: Vector2f64) -> Option<(usize, Vec<usize>)> {
+        let index = self.vertices.len();
+        self.vertices.push(point);
+        self.inserted.push(false);
+        self.point_triangles.push(0);
+
+        match self.insert(index) {
+            Some(changed) => {
+                self.inserted[index] = true;
+                Some((index, changed))
+            }
+            None => {
+                self.vertices.pop();
+                self.inserted.pop();
+                self.point_triangles.pop();
+                None
+            }
+        }
+    }
+
+    /// Removes an inserted point and re-triangulates the hole it leaves. Returns the points whose
+    /// Delaunay neighbours changed.
+    pub(crate) fn remove(&mut self, index: usize) -> Option<Vec<usize>> {
+        if !self.inserted.get(index).copied().unwrap_or(false) {
+            return None;
+        }
+
+        let (mut ring, triangles) = self.get_star(index);
+        for triangle in triangles {
+            self.remove_triangle(triangle);
+        }
+
+        self.inserted[index] = false;
+
+        let changed = ring
+            .iter()
+            .copied()
+            .filter(|vertex| !self.is_super_vertex(*vertex))
+            .collect();
+
+        // INFO: cuts off ears whose circumcircle holds no other vertex of the hole, these are
+        // Delaunay triangles of the points around the hole
+        while ring.len() > 3 {
+            let ear = (0..ring.len())
+                .find(|position| self.is_delaunay_ear(&ring, *position))
+                .or_else(|| (0..ring.len()).find(|position| self.is_convex_ear(&ring, *position)))
+                .unwrap_or(0);
+
+            let count = ring.len();
+            let previous = ring[(ear + count - 1) % count];
+            let next = ring[(ear + 1) % count];
+            self.add_triangle([previous, ring[ear], next]);
+            ring.remove(ear);
+        }
+
+        self.add_triangle([ring[0], ring[1], ring[2]]);
+
+        Some(changed)
+    }
+
+    /// Returns the neighbouring vertices of an inserted point in counterclockwise order, super
+    /// vertices included.
+    pub(crate) fn get_vertex_neighbours(&self, index: usize) -> Vec<usize> {
+        self.get_star(index).0
+    }
+
+    /// Returns the inserted point closest to `point`, found by walking the Delaunay graph.
+    pub(crate) fn get_nearest_point(&self, point: Vector2f64) -> Option<usize> {
+        let triangle = self.triangles[self.locate(point)].unwrap();
+
+        let mut nearest = triangle
+            .into_iter()
+            .find(|vertex| !self.is_super_vertex(*vertex))?;
+        let mut distance = (self.vertices[nearest] - point).norm_squared();
+
+        'walk: loop {
+            for neighbour in self.get_vertex_neighbours(nearest) {
+                if self.is_super_vertex(neighbour) {
+                    continue;
+                }
+
+                let neighbour_distance = (self.vertices[neighbour] - point).norm_squared();
+                if neighbour_distance < distance {
+                    nearest = neighbour;
+                    distance = neighbour_distance;
+                    continue 'walk;
+                }
+            }
+
+            return Some(nearest);
+        }
+    }
+
+    /// Returns the ring of neighbours around an inserted point and the triangles between them.
+    fn get_star(&self, index: usize) -> (Vec<usize>, Vec<usize>) {
+        let mut ring = Vec::new();
+        let mut triangles = Vec::new();
+
+        let mut triangle = self.point_triangles[index];
+        loop {
+            let [_, current, next] = rotate_to(self.triangles[triangle].unwrap(), index);
+            ring.push(current);
+            triangles.push(triangle);
+
+            triangle = self.edges[&(index, next)];
+            if triangle == triangles[0] {
+                return (ring, triangles);
+            }
+        }
+    }
+
+    fn is_convex_ear(&self, ring: &[usize], position: usize) -> bool {
+        let count = ring.len();
+        let [a, b, c] = [position + count - 1, position, position + 1]
+            .map(|position| self.get_vertex(ring[position % count]));
+
+        predicates::orient2d(a, b, c) > 0.0
+    }
+
+    fn is_delaunay_ear(&self, ring: &[usize], position: usize) -> bool {
+        if !self.is_convex_ear(ring, position) {
+            return false;
+        }
+
+        let count = ring.len();
+        let [a, b, c] = [position + count - 1, position, position + 1]
+            .map(|position| self.get_vertex(ring[position % count]));
+
+        (2..count - 1).all(|offset| {
+            let other = self.get_vertex(ring[(position + offset) % count]);
+            predicates::incircle(a, b, c, other) <= 0.0
+        })
+    }
+
+    fn insert(&mut self, index: usize) -> Option<Vec<usize>> {
+        let point = self.vertices[index];
+
+        let start = self.locate(point);
+        let start_triangle = self.triangles[start].unwrap();
+
+        // INFO: duplicates lie on the circumcircles of their twins and never form a cavity
+        if start_triangle
+            .iter()
+            .any(|vertex| self.get_vertex(*vertex) == point)
+        {
+            return None;
+        }
+
+        let mut cavity = vec![start];
+        let mut boundary = Vec::<(usize, usize)>::new();
+        let mut position = 0;
+        while position < cavity.len() {
+            let [a, b, c] = self.triangles[cavity[position]].unwrap();
+            position += 1;
+
+            for (start, end) in [(a, b), (b, c), (c, a)] {
+                match self.edges.get(&(end, start)) {
+                    Some(neighbour) if cavity.contains(neighbour) => (),
+                    Some(neighbour) if self.is_in_circumcircle(*neighbour, point) => {
+                        cavity.push(*neighbour);
+                    }
+                    _ => boundary.push((start, end)),
+                }
+            }
+        }
+
+        for triangle in cavity {
+            self.remove_triangle(triangle);
+        }
+
+        let mut changed = Vec::with_capacity(boundary.len());
+        for (start, end) in boundary {
+            self.add_triangle([start, end, index]);
+
+            if !self.is_super_vertex(start) {
+                changed.push(start);
+            }
+        }
+
+        Some(changed)
+    }
+
+    /// Walks from the last touched triangle towards the point and returns a triangle containing
+    /// it.
+    fn locate(&self, point: Vector2f64) -> usize {
+        let mut current = self.last_triangle;
+
+        'walk: loop {
+            let [a, b, c] = self.triangles[current].unwrap();
+
+            for (start, end) in [(a, b), (b, c), (c, a)] {
+                let orientation =
+                    predicates::orient2d(self.get_vertex(start), self.get_vertex(end), point);
+
+                if orientation < 0.0 {
+                    if let Some(neighbour) = self.edges.get(&(end, start)) {
+                        current = *neighbour;
+                        continue 'walk;
+                    }
+                }
+            }
+
+            return current;
+        }
+    }
+
+    fn is_in_circumcircle(&self, triangle: usize, point: Vector2f64) -> bool {
+        let [a, b, c] = self.triangles[triangle]
+            .unwrap()
+            .map(|vertex| self.get_vertex(vertex));
+
+        predicates::incircle(a, b, c, point) > 0.0
+    }
+
+    fn add_triangle(&mut self, triangle: Triangle) {
+        let index = if let Some(index) = self.free_triangles.pop() {
+            self.triangles[index] = Some(triangle);
+            index
+        } else {
+            self.triangles.push(Some(triangle));
+            self.triangles.len() - 1
+        };
+
+        let [a, b, c] = triangle;
+        for edge in [(a, b), (b, c), (c, a)] {
+            self.edges.insert(edge, index);
+        }
+
+        for vertex in triangle {
+            if !self.is_super_vertex(vertex) {
+                self.point_triangles[vertex] = index;
+            }
+        }
+
+        self.last_triangle = index;
+    }
+
+    fn remove_triangle(&mut self, index: usize) {
+        if let Some([a, b, c]) = self.triangles[index].take() {
+            for edge in [(a, b), (b, c), (c, a)] {
+                self.edges.remove(&edge);
+            }
+
+            self.free_triangles.push(index);
+        }
+    }
+
+    /// Returns candidate Voronoi neighbours per point. Points on the convex hull are connected to
+    /// each other as the super triangle can suppress Delaunay edges along the hull.
+    pub(crate) fn get_neighbours(&self) -> Vec<Vec<usize>> {
+        let point_count = self.point_count();
+
+        let mut neighbours = vec![Vec::<usize>::new(); point_count];
+        let mut is_hull = vec![false; point_count];
+
+        for (start, end) in self.edges.keys() {
+            if self.is_super_vertex(*start) {
+                continue;
+            }
+
+            if self.is_super_vertex(*end) {
+                is_hull[*start] = true;
+            } else {
+                neighbours[*start].push(*end);
+            }
+        }
+
+        let hull: Vec<usize> = (0..point_count).filter(|index| is_hull[*index]).collect();
+        for vertex in hull.iter() {
+            for other in hull.iter() {
+                if vertex != other && !neighbours[*vertex].contains(other) {
+                    neighbours[*vertex].push(*other);
+                }
+            }
+        }
+
+        // INFO: the edges are stored unordered, sorting keeps the generated cells reproducible
+        for candidates in neighbours.iter_mut() {
+            candidates.sort_unstable();
+        }
+
+        neighbours
+    }
+}
+
+fn rotate_to(triangle: Triangle, vertex: usize) -> Triangle {
+    let [a, b, c] = triangle;
+    if a == vertex {
+        [a, b, c]
+    } else if b == vertex {
+        [b, c, a]
+    } else {
+        [c, a, b]
+    }
+}
+
+fn generate_super_triangle(min: Vector2f64, max: Vector2f64) -> [Vector2f64; 3] {
+    let center = (min + max) / 2.0;
+    let extent = (max - min).max().max(1.0) * SUPER_TRIANGLE_SCALE;
+
+    [
+        center + Vector2f64::new(-2.0 * extent, -extent),
+        center + Vector2f64::new(2.0 * extent, -extent),
+        center + Vector2f64::new(0.0, 2.0 * extent),
+    ]
+}
+
+fn get_bounding_box(points: &[Vector2f64]) -> (Vector2f64, Vector2f64) {
+    if points.is_empty() {
+        return (Vector2f64::zeros(), Vector2f64::zeros());
+    }
+
+    points
+        .iter()
+        .fold((points[0], points[0]), |(min, max), point| {
+            (min.inf(point), max.sup(point))
+        })
+}
+
+/// Orders points row by row in alternating direction over a coarse grid, so consecutive
+/// insertions are close to each other and the walk in `locate` stays short.
+fn get_insertion_order(points: &[Vector2f64]) -> Vec<usize> {
+    let (min, max) = get_bounding_box(points);
+    let size = (max - min).sup(&Vector2f64::repeat(f64::EPSILON));
+    let rows = (points.len() as f64).sqrt().ceil().max(1.0);
+
+    let get_key = |point: Vector2f64| {
+        let row = ((point.y - min.y) / size.y * rows).floor().min(rows - 1.0);
+        let column = (point.x - min.x) / size.x;
+
+        if row % 2.0 == 0.0 {
+            (row, column)
+        } else {
+            (row, -column)
+        }
+    };
+
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|a, b| {
+        let (a_row, a_column) = get_key(points[*a]);
+        let (b_row, b_column) = get_key(points[*b]);
+
+        a_row.total_cmp(&b_row).then(a_column.total_cmp(&b_column))
+    });
+
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::{predicates, Vector2f64};
+
+    use super::{Triangulation, SUPER_VERTEX};
+
+    fn get_triangles(triangulation: &Triangulation) -> Vec<[usize; 3]> {
+        triangulation.triangles.iter().flatten().copied().collect()
+    }
+
+    fn assert_empty_circumcircles(triangulation: &Triangulation) {
+        let inserted: Vec<usize> = (0..triangulation.point_count())
+            .filter(|index| triangulation.inserted[*index])
+            .chain(SUPER_VERTEX..=SUPER_VERTEX + 2)
+            .collect();
+
+        for [a, b, c] in get_triangles(triangulation) {
+            let [pa, pb, pc] = [a, b, c].map(|vertex| triangulation.get_vertex(vertex));
+            assert!(
+                predicates::orient2d(pa, pb, pc) > 0.0,
+                "triangle is not ccw"
+            );
+
+            for vertex in inserted.iter() {
+                let point = triangulation.get_vertex(*vertex);
+                assert!(
+                    predicates::incircle(pa, pb, pc, point) <= 0.0,
+                    "vertex {} lies inside circumcircle of {:?}",
+                    vertex,
+                    [a, b, c]
+                );
+            }
+        }
+    }
+
+    fn assert_euler_characteristic(triangulation: &Triangulation) {
+        let triangles = get_triangles(triangulation);
+
+        let mut vertices = HashSet::new();
+        let mut edges = HashSet::new();
+        for [a, b, c] in triangles.iter() {
+            for (start, end) in [(*a, *b), (*b, *c), (*c, *a)] {
+                vertices.insert(start);
+                edges.insert((start.min(end), start.max(end)));
+            }
+        }
+
+        let inserted_count = triangulation.inserted.iter().filter(|i| **i).count();
+        assert_eq!(vertices.len(), inserted_count + 3, "vertex missing");
+
+        // INFO: a triangulated disk has V - E + F = 1
+        let characteristic =
+            vertices.len() as isize - edges.len() as isize + triangles.len() as isize;
+        assert_eq!(characteristic, 1);
+    }
+
+    fn assert_delaunay(points: &[Vector2f64]) {
+        let triangulation = Triangulation::new(points);
+
+        assert_empty_circumcircles(&triangulation);
+        assert_euler_characteristic(&triangulation);
+    }
+
+    #[test]
+    fn random_points_are_delaunay() {
+        for seed in 0..50 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let count = rng.gen_range(1..200);
+            let points: Vec<Vector2f64> = (0..count)
+                .map(|_| {
+                    Vector2f64::new(rng.gen_range(-100.0..100.0), rng.gen_range(-100.0..100.0))
+                })
+                .collect();
+
+            assert_delaunay(&points);
+        }
+    }
+
+    #[test]
+    fn pixel_centres_are_delaunay() {
+        let points: Vec<Vector2f64> = (0..32 * 32)
+            .map(|index| Vector2f64::new((index % 32) as f64 + 0.5, (index / 32) as f64 + 0.5))
+            .collect();
+
+        assert_delaunay(&points);
+    }
+
+    #[test]
+    fn nearly_degenerate_grid_is_delaunay() {
+        for seed in 0..20 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let points: Vec<Vector2f64> = (0..16 * 16)
+                .map(|index| {
+                    let jitter =
+                        Vector2f64::new(rng.gen_range(-1e-12..1e-12), rng.gen_range(-1e-12..1e-12));
+                    Vector2f64::new((index % 16) as f64, (index / 16) as f64) * 0.1 + jitter
+                })
+                .collect();
+
+            assert_delaunay(&points);
+        }
+    }
+
+    #[test]
+    fn cocircular_points_are_delaunay() {
+        let points: Vec<Vector2f64> = (0..64)
+            .map(|index| {
+                let angle = index as f64 / 64.0 * std::f64::consts::TAU;
+                Vector2f64::new(angle.cos(), angle.sin()) * 10.0
+            })
+            .collect();
+
+        assert_delaunay(&points);
+    }
+
+    #[test]
+    fn collinear_points_are_delaunay() {
+        let points: Vec<Vector2f64> = (0..50)
+            .map(|index| Vector2f64::new(index as f64 * 0.3, index as f64 * 0.7))
+            .collect();
+
+        assert_delaunay(&points);
+    }
+
+    #[test]
+    fn duplicates_are_skipped() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut points: Vec<Vector2f64> = (0..100)
+            .map(|_| Vector2f64::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+            .collect();
+        points.extend(points.clone());
+
+        let triangulation = Triangulation::new(&points);
+
+        assert_eq!(triangulation.inserted.iter().filter(|i| **i).count(), 100);
+        assert_empty_circumcircles(&triangulation);
+        assert_euler_characteristic(&triangulation);
+    }
+
+    #[test]
+    fn incremental_changes_are_delaunay() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut triangulation =
+            Triangulation::with_bounding_box(Vector2f64::zeros(), Vector2f64::repeat(10.0));
+
+        let mut indices = Vec::new();
+        for _ in 0..300 {
+            let point = Vector2f64::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0));
+            if let Some((index, _)) = triangulation.push(point) {
+                indices.push(index);
+            }
+
+            if rng.gen_bool(0.3) {
+                let index = indices.swap_remove(rng.gen_range(0..indices.len()));
+                assert!(triangulation.remove(index).is_some());
+                assert!(triangulation.remove(index).is_none());
+            }
+        }
+
+        assert_empty_circumcircles(&triangulation);
+        assert_euler_characteristic(&triangulation);
+
+        for index in indices {
+            triangulation.remove(index);
+        }
+
+        assert_euler_characteristic(&triangulation);
+    }
+
+    #[test]
+    fn removing_grid_points_is_delaunay() {
+        let mut triangulation =
+            Triangulation::with_bounding_box(Vector2f64::zeros(), Vector2f64::repeat(16.0));
+        for index in 0..16 * 16 {
+            triangulation.push(Vector2f64::new((index % 16) as f64, (index / 16) as f64));
+        }
+
+        for index in (0..16 * 16).step_by(3) {
+            triangulation.remove(index);
+        }
+
+        assert_empty_circumcircles(&triangulation);
+        assert_euler_characteristic(&triangulation);
+    }
+}
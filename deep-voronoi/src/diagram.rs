@@ -0,0 +1,407 @@
+use crate::{bounds::Bounds, delaunay::Triangulation, index::SiteIndex, Vector2f64};
+
+// INFO: power cells are clipped by this many nearest sites before bounding the remaining ones
+const POWER_CANDIDATES: usize = 12;
+// INFO: edges shorter than this fraction of the bounds diagonal are treated as touching corners
+// instead of shared edges
+const EDGE_EPSILON: f64 = 1e-9;
+
+#[derive(Clone, Debug)]
+pub struct Cell {
+    pub site: Vector2f64,
+    /// Counterclockwise outline of the cell. Empty if the site is a duplicate of another site or
+    /// is dominated by heavier sites in a power diagram.
+    pub polygon: Vec<Vector2f64>,
+    pub neighbours: Vec<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Diagram {
+    pub bounds: Bounds,
+    pub cells: Vec<Cell>,
+    pub periodic: bool,
+    index: SiteIndex,
+    weights: Option<Vec<f64>>,
+}
+
+/// Site of a power diagram. The weight is a squared radius: a point belongs to the site with
+/// the smallest power distance `|point - position|² - weight`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WeightedSite {
+    pub position: Vector2f64,
+    pub weight: f64,
+}
+
+impl Diagram {
+    /// Returns the index of the cell containing `point`. Periodic diagrams wrap the point into
+    /// their bounds first.
+    pub fn get_cell_index(&self, point: Vector2f64) -> Option<usize> {
+        let point = self.get_domain_point(point);
+        let nearest = self.index.nearest(point)?;
+
+        Some(self.get_power_cell_index(point, nearest))
+    }
+
+    /// Returns up to `count` site positions ordered by distance. For periodic diagrams these are
+    /// the closest images of the sites relative to the wrapped point.
+    pub(crate) fn get_nearest_sites(&self, point: Vector2f64, count: usize) -> Vec<Vector2f64> {
+        let point = self.get_domain_point(point);
+
+        self.index
+            .k_nearest(point, count)
+            .into_iter()
+            .map(|index| self.index.site(index))
+            .collect()
+    }
+
+    fn get_domain_point(&self, point: Vector2f64) -> Vector2f64 {
+        if self.periodic {
+            self.bounds.wrap(point)
+        } else {
+            point
+        }
+    }
+
+    /// Unweighted diagrams use a weight of zero, so the power is the squared distance. Ties
+    /// between coincident sites resolve to the one with a non-empty cell.
+    fn get_power_cell_index(&self, point: Vector2f64, nearest: usize) -> usize {
+        let get_weight = |index: usize| self.weights.as_ref().map_or(0.0, |weights| weights[index]);
+        let get_power =
+            |index: usize| (self.index.site(index) - point).norm_squared() - get_weight(index);
+        let is_empty = |index: usize| self.cells[index % self.cells.len()].polygon.is_empty();
+
+        // INFO: a site with a smaller power is closer than sqrt(power of nearest + max weight)
+        let weight_max = self.weights.as_ref().map_or(0.0, |weights| {
+            weights.iter().copied().fold(f64::MIN, f64::max)
+        });
+        let radius = (get_power(nearest) + weight_max).max(0.0).sqrt();
+
+        let closest =
+            self.index
+                .within_radius(point, radius)
+                .into_iter()
+                .fold(nearest, |best, index| {
+                    let (power, best_power) = (get_power(index), get_power(best));
+                    if power < best_power || (power == best_power && is_empty(best)) {
+                        index
+                    } else {
+                        best
+                    }
+                });
+
+        closest % self.cells.len()
+    }
+}
+
+// INFO: vertex with the source of the edge towards the next vertex, None for the initial outline
+type LabeledVertex = (Vector2f64, Option<usize>);
+
+pub(crate) fn generate(sites: &[Vector2f64], bounds: &Bounds) -> Diagram {
+    let triangulation = Triangulation::new(sites);
+    let neighbours = triangulation.get_neighbours();
+
+    let outline = get_rectangle(bounds.min, bounds.max);
+    let tolerance = get_edge_tolerance(bounds);
+    let cells = (0..sites.len())
+        .map(|index| {
+            if !triangulation.inserted[index] {
+                return get_empty_cell(sites[index]);
+            }
+
+            let candidates = &neighbours[index];
+            generate_cell(
+                sites,
+                None,
+                index,
+                candidates,
+                outline.clone(),
+                tolerance,
+                |n| n,
+            )
+        })
+        .collect();
+
+    Diagram {
+        bounds: *bounds,
+        cells,
+        periodic: false,
+        index: SiteIndex::new(sites),
+        weights: None,
+    }
+}
+
+/// The Delaunay neighbours of the unweighted sites do not cover all power neighbours. Cells of a
+/// power diagram are clipped by the nearest sites first, which bounds the distance of every other
+/// site able to cut the cell further.
+pub(crate) fn generate_power(sites: &[WeightedSite], bounds: &Bounds) -> Diagram {
+    let positions: Vec<Vector2f64> = sites.iter().map(|site| site.position).collect();
+    let weights: Vec<f64> = sites.iter().map(|site| site.weight).collect();
+    let index = SiteIndex::new(&positions);
+
+    let outline = get_rectangle(bounds.min, bounds.max);
+    let tolerance = get_edge_tolerance(bounds);
+    let weight_max = weights.iter().copied().fold(f64::MIN, f64::max);
+
+    // INFO: coincident sites keep the heavier one, equal weights keep the lower index
+    let dominated: Vec<bool> = (0..sites.len())
+        .map(|site_index| {
+            index
+                .within_radius(positions[site_index], 0.0)
+                .into_iter()
+                .any(|other| {
+                    other != site_index
+                        && (weights[other] > weights[site_index]
+                            || (weights[other] == weights[site_index] && other < site_index))
+                })
+        })
+        .collect();
+
+    let cells = (0..sites.len())
+        .map(|site_index| {
+            let site = positions[site_index];
+            if dominated[site_index] {
+                return get_empty_cell(site);
+            }
+
+            let mut candidates: Vec<usize> = index
+                .k_nearest(site, POWER_CANDIDATES + 1)
+                .into_iter()
+                .filter(|n| *n != site_index && !dominated[*n])
+                .collect();
+
+            let cell = generate_cell(
+                &positions,
+                Some(&weights),
+                site_index,
+                &candidates,
+                outline.clone(),
+                tolerance,
+                |n| n,
+            );
+
+            // INFO: for a vertex at distance r from the site and another site at distance d > r,
+            // the other power is at least (d - r)² - max weight, so only sites closer than
+            // r + sqrt(r² - weight + max weight) may have a smaller power at a vertex
+            let radius = match get_radius(site, &cell.polygon) {
+                Some(radius) => radius,
+                None => return cell,
+            };
+            let reach = radius
+                + (radius * radius - weights[site_index] + weight_max)
+                    .max(0.0)
+                    .sqrt();
+
+            let remaining: Vec<usize> = index
+                .within_radius(site, reach)
+                .into_iter()
+                .filter(|n| *n != site_index && !dominated[*n] && !candidates.contains(n))
+                .collect();
+            if remaining.is_empty() {
+                return cell;
+            }
+
+            candidates.extend(remaining);
+            generate_cell(
+                &positions,
+                Some(&weights),
+                site_index,
+                &candidates,
+                outline.clone(),
+                tolerance,
+                |n| n,
+            )
+        })
+        .collect();
+
+    Diagram {
+        bounds: *bounds,
+        cells,
+        periodic: false,
+        index,
+        weights: Some(weights),
+    }
+}
+
+fn get_radius(site: Vector2f64, polygon: &[Vector2f64]) -> Option<f64> {
+    polygon
+        .iter()
+        .map(|vertex| (vertex - site).norm())
+        .reduce(f64::max)
+}
+
+/// Sites wrap across the bounds, so cells leaving one side continue on the opposite side. Cell
+/// polygons are not wrapped and can extend beyond the bounds around their site.
+pub(crate) fn generate_periodic(sites: &[Vector2f64], bounds: &Bounds) -> Diagram {
+    let sites: Vec<Vector2f64> = sites.iter().map(|site| bounds.wrap(*site)).collect();
+    let site_count = sites.len();
+
+    // INFO: the central tile comes first to keep the indices of the original sites
+    let mut tiled_sites = sites.clone();
+    for (x, y) in get_tile_offsets() {
+        let offset = Vector2f64::new(x * bounds.width(), y * bounds.height());
+        tiled_sites.extend(sites.iter().map(|site| site + offset));
+    }
+
+    let triangulation = Triangulation::new(&tiled_sites);
+    let neighbours = triangulation.get_neighbours();
+
+    // INFO: a cell never exceeds the bounds centered at its site due to its own images
+    let size = Vector2f64::new(bounds.width(), bounds.height());
+    let tolerance = get_edge_tolerance(bounds);
+    let cells = (0..site_count)
+        .map(|index| {
+            if !triangulation.inserted[index] {
+                return get_empty_cell(sites[index]);
+            }
+
+            let site = tiled_sites[index];
+            let outline = get_rectangle(site - size, site + size);
+            let candidates = &neighbours[index];
+            let mut cell = generate_cell(
+                &tiled_sites,
+                None,
+                index,
+                candidates,
+                outline,
+                tolerance,
+                |n| n % site_count,
+            );
+
+            cell.neighbours.retain(|neighbour| *neighbour != index);
+            cell
+        })
+        .collect();
+
+    Diagram {
+        bounds: *bounds,
+        cells,
+        periodic: true,
+        index: SiteIndex::new(&tiled_sites),
+        weights: None,
+    }
+}
+
+fn get_tile_offsets() -> Vec<(f64, f64)> {
+    let mut offsets = Vec::new();
+    for x in [-1.0, 0.0, 1.0] {
+        for y in [-1.0, 0.0, 1.0] {
+            if x != 0.0 || y != 0.0 {
+                offsets.push((x, y));
+            }
+        }
+    }
+
+    offsets
+}
+
+pub(crate) fn get_rectangle(min: Vector2f64, max: Vector2f64) -> Vec<LabeledVertex> {
+    vec![
+        (min, None),
+        (Vector2f64::new(max.x, min.y), None),
+        (max, None),
+        (Vector2f64::new(min.x, max.y), None),
+    ]
+}
+
+pub(crate) fn get_edge_tolerance(bounds: &Bounds) -> f64 {
+    EDGE_EPSILON * (bounds.max - bounds.min).norm()
+}
+
+fn get_empty_cell(site: Vector2f64) -> Cell {
+    Cell {
+        site,
+        polygon: vec![],
+        neighbours: vec![],
+    }
+}
+
+pub(crate) fn generate_cell<F>(
+    sites: &[Vector2f64],
+    weights: Option<&[f64]>,
+    index: usize,
+    candidates: &[usize],
+    outline: Vec<LabeledVertex>,
+    tolerance: f64,
+    get_neighbour: F,
+) -> Cell
+where
+    F: Fn(usize) -> usize,
+{
+    let site = sites[index];
+    let get_weight = |index: usize| weights.map_or(0.0, |weights| weights[index]);
+
+    let mut polygon = outline;
+    for candidate in candidates {
+        // INFO: points with a smaller power to site than to the candidate satisfy normal * x <= offset
+        let other = sites[*candidate];
+        let normal = other - site;
+        let offset = (other.norm_squared() - site.norm_squared() + get_weight(index)
+            - get_weight(*candidate))
+            / 2.0;
+
+        // INFO: coincident sites keep the heavier one, equal weights keep the lower index
+        if normal == Vector2f64::zeros() {
+            if offset < 0.0 || (offset == 0.0 && *candidate < index) {
+                polygon.clear();
+            }
+
+            continue;
+        }
+
+        polygon = clip(&polygon, normal, offset, *candidate);
+    }
+
+    let mut neighbours = Vec::new();
+    for (position, (start, label)) in polygon.iter().enumerate() {
+        let (end, _) = polygon[(position + 1) % polygon.len()];
+        if let Some(label) = label {
+            let neighbour = get_neighbour(*label);
+            if (end - start).norm() > tolerance && !neighbours.contains(&neighbour) {
+                neighbours.push(neighbour);
+            }
+        }
+    }
+
+    Cell {
+        site,
+        polygon: polygon.into_iter().map(|(vertex, _)| vertex).collect(),
+        neighbours,
+    }
+}
+
+// https://en.wikipedia.org/wiki/Sutherland%E2%80%93Hodgman_algorithm
+fn clip(
+    polygon: &[LabeledVertex],
+    normal: Vector2f64,
+    offset: f64,
+    label: usize,
+) -> Vec<LabeledVertex> {
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+
+    for (position, (current, current_label)) in polygon.iter().enumerate() {
+        let (next, _) = polygon[(position + 1) % polygon.len()];
+
+        let current_distance = normal.dot(current) - offset;
+        let next_distance = normal.dot(&next) - offset;
+
+        let current_inside = current_distance <= 0.0;
+        let next_inside = next_distance <= 0.0;
+
+        if current_inside {
+            clipped.push((*current, *current_label));
+        }
+
+        if current_inside != next_inside {
+            let t = current_distance / (current_distance - next_distance);
+            let intersection = current + (next - current) * t;
+
+            if current_inside {
+                clipped.push((intersection, Some(label)));
+            } else {
+                clipped.push((intersection, *current_label));
+            }
+        }
+    }
+
+    clipped
+}
@@ -0,0 +1,127 @@
+use std::collections::BTreeSet;
+
+use crate::{
+    bounds::Bounds,
+    delaunay::Triangulation,
+    diagram::{self, Cell},
+    Vector2f64,
+};
+
+/// Voronoi diagram clipped to its bounds that supports inserting and removing sites. Only the
+/// cells around a changed site are regenerated, their ids are collected until they get taken
+/// with `take_changed_cells`. Ids of removed sites are never reused.
+pub struct DynamicDiagram {
+    bounds: Bounds,
+    triangulation: Triangulation,
+    cells: Vec<Option<Cell>>,
+    changed_cells: BTreeSet<usize>,
+}
+
+impl DynamicDiagram {
+    pub fn new(bounds: &Bounds) -> Self {
+        Self {
+            bounds: *bounds,
+            triangulation: Triangulation::with_bounding_box(bounds.min, bounds.max),
+            cells: Vec::new(),
+            changed_cells: BTreeSet::new(),
+        }
+    }
+
+    pub fn bounds(&self) -> &Bounds {
+        &self.bounds
+    }
+
+    /// Returns the number of sites currently in the diagram.
+    pub fn len(&self) -> usize {
+        self.cells.iter().flatten().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.iter().all(Option::is_none)
+    }
+
+    pub fn get_cell(&self, id: usize) -> Option<&Cell> {
+        self.cells.get(id)?.as_ref()
+    }
+
+    /// Iterates over the ids and cells of all sites in the diagram.
+    pub fn cells(&self) -> impl Iterator<Item = (usize, &Cell)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(|(id, cell)| cell.as_ref().map(|cell| (id, cell)))
+    }
+
+    /// Returns the id of the cell containing `point`.
+    pub fn get_cell_id(&self, point: Vector2f64) -> Option<usize> {
+        self.triangulation.get_nearest_point(point)
+    }
+
+    /// Inserts a site and returns its id. Sites outside of the bounds or duplicating another site
+    /// are rejected.
+    pub fn insert(&mut self, site: Vector2f64) -> Option<usize> {
+        if !self.bounds.contains(site) {
+            return None;
+        }
+
+        let (id, changed) = self.triangulation.push(site)?;
+        self.cells.push(None);
+
+        self.update_cell(id);
+        for neighbour in changed {
+            self.update_cell(neighbour);
+        }
+
+        Some(id)
+    }
+
+    /// Removes a site and returns whether it was part of the diagram.
+    pub fn remove(&mut self, id: usize) -> bool {
+        let changed = match self.triangulation.remove(id) {
+            Some(changed) => changed,
+            None => return false,
+        };
+
+        self.cells[id] = None;
+        self.changed_cells.insert(id);
+
+        for neighbour in changed {
+            self.update_cell(neighbour);
+        }
+
+        true
+    }
+
+    /// Returns the ids of all cells inserted, removed or reshaped since the last call in
+    /// ascending order.
+    pub fn take_changed_cells(&mut self) -> Vec<usize> {
+        std::mem::take(&mut self.changed_cells)
+            .into_iter()
+            .collect()
+    }
+
+    fn update_cell(&mut self, id: usize) {
+        let candidates: Vec<usize> = self
+            .triangulation
+            .get_vertex_neighbours(id)
+            .into_iter()
+            .filter(|neighbour| !self.triangulation.is_super_vertex(*neighbour))
+            .collect();
+
+        // INFO: the super triangle encloses the bounds, so Delaunay edges it suppresses only
+        // matter far outside of them and the neighbours of the site are sufficient
+        let outline = diagram::get_rectangle(self.bounds.min, self.bounds.max);
+        let cell = diagram::generate_cell(
+            &self.triangulation.vertices,
+            None,
+            id,
+            &candidates,
+            outline,
+            diagram::get_edge_tolerance(&self.bounds),
+            |n| n,
+        );
+
+        self.cells[id] = Some(cell);
+        self.changed_cells.insert(id);
+    }
+}
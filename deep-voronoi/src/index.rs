@@ -0,0 +1,141 @@
+use crate::Vector2f64;
+
+/// Static 2d tree over the sites of a diagram. The tree is stored implicitly: every range of
+/// `order` has its splitting site at the median position, alternating between the x and y axis.
+#[derive(Clone, Debug)]
+pub struct SiteIndex {
+    sites: Vec<Vector2f64>,
+    order: Vec<usize>,
+}
+
+impl SiteIndex {
+    pub fn new(sites: &[Vector2f64]) -> Self {
+        let mut order: Vec<usize> = (0..sites.len()).collect();
+        build(sites, &mut order, 0);
+
+        Self {
+            sites: sites.to_vec(),
+            order,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.sites.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sites.is_empty()
+    }
+
+    pub fn site(&self, index: usize) -> Vector2f64 {
+        self.sites[index]
+    }
+
+    pub fn nearest(&self, point: Vector2f64) -> Option<usize> {
+        self.k_nearest(point, 1).first().copied()
+    }
+
+    /// Returns up to `k` site indices ordered by ascending distance to `point`.
+    pub fn k_nearest(&self, point: Vector2f64, k: usize) -> Vec<usize> {
+        let mut nearest = Vec::<(f64, usize)>::with_capacity(k + 1);
+        if k > 0 {
+            self.search_nearest(point, k, 0, self.order.len(), 0, &mut nearest);
+        }
+
+        nearest.into_iter().map(|(_, index)| index).collect()
+    }
+
+    /// Returns all site indices within `radius` of `point` in no particular order.
+    pub fn within_radius(&self, point: Vector2f64, radius: f64) -> Vec<usize> {
+        let mut found = Vec::new();
+        self.search_radius(point, radius * radius, 0, self.order.len(), 0, &mut found);
+
+        found
+    }
+
+    fn search_nearest(
+        &self,
+        point: Vector2f64,
+        k: usize,
+        start: usize,
+        end: usize,
+        depth: usize,
+        nearest: &mut Vec<(f64, usize)>,
+    ) {
+        if start >= end {
+            return;
+        }
+
+        let median = (start + end) / 2;
+        let index = self.order[median];
+        let site = self.sites[index];
+
+        let distance = (site - point).norm_squared();
+        if nearest.len() < k || distance < nearest[nearest.len() - 1].0 {
+            let position = nearest.partition_point(|(other, _)| *other <= distance);
+            nearest.insert(position, (distance, index));
+            nearest.truncate(k);
+        }
+
+        let axis = depth % 2;
+        let difference = point[axis] - site[axis];
+        let (near, far) = if difference < 0.0 {
+            ((start, median), (median + 1, end))
+        } else {
+            ((median + 1, end), (start, median))
+        };
+
+        self.search_nearest(point, k, near.0, near.1, depth + 1, nearest);
+
+        if nearest.len() < k || difference * difference < nearest[nearest.len() - 1].0 {
+            self.search_nearest(point, k, far.0, far.1, depth + 1, nearest);
+        }
+    }
+
+    fn search_radius(
+        &self,
+        point: Vector2f64,
+        radius_squared: f64,
+        start: usize,
+        end: usize,
+        depth: usize,
+        found: &mut Vec<usize>,
+    ) {
+        if start >= end {
+            return;
+        }
+
+        let median = (start + end) / 2;
+        let index = self.order[median];
+        let site = self.sites[index];
+
+        if (site - point).norm_squared() <= radius_squared {
+            found.push(index);
+        }
+
+        let axis = depth % 2;
+        let difference = point[axis] - site[axis];
+
+        if difference <= 0.0 || difference * difference <= radius_squared {
+            self.search_radius(point, radius_squared, start, median, depth + 1, found);
+        }
+
+        if difference >= 0.0 || difference * difference <= radius_squared {
+            self.search_radius(point, radius_squared, median + 1, end, depth + 1, found);
+        }
+    }
+}
+
+fn build(sites: &[Vector2f64], order: &mut [usize], depth: usize) {
+    if order.len() <= 1 {
+        return;
+    }
+
+    let axis = depth % 2;
+    let median = order.len() / 2;
+    order.select_nth_unstable_by(median, |a, b| sites[*a][axis].total_cmp(&sites[*b][axis]));
+
+    let (left, right) = order.split_at_mut(median);
+    build(sites, left, depth + 1);
+    build(sites, &mut right[1..], depth + 1);
+}
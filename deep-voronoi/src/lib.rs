@@ -0,0 +1,97 @@
+use image::{ImageBuffer, Luma};
+use nalgebra::{Vector2, Vector3};
+
+pub use bounds::Bounds;
+pub use diagram::{Cell, Diagram, WeightedSite};
+pub use dynamic::DynamicDiagram;
+pub use index::SiteIndex;
+pub use raster::{CellIdMap, DistanceMap, Raster};
+pub use tectonic::{Plate, TectonicMap, TectonicSettings};
+
+mod bounds;
+mod delaunay;
+mod diagram;
+mod dynamic;
+mod index;
+mod poisson;
+mod predicates;
+mod raster;
+mod tectonic;
+mod worley;
+
+pub type Vector2f32 = Vector2<f32>;
+pub type Vector2f64 = Vector2<f64>;
+pub type Vector3f32 = Vector3<f32>;
+
+pub fn sample_poisson_disk(bounds: &Bounds, radius: f64, seed: u64) -> Vec<Vector2f64> {
+    poisson::generate(bounds, radius, radius, |_| radius, seed)
+}
+
+/// Samples with a radius varying between `radius_min` and `radius_max`, where a density of 1.0
+/// results in `radius_min` and a density of 0.0 in `radius_max`.
+pub fn sample_poisson_disk_by_density<F>(
+    bounds: &Bounds,
+    radius_min: f64,
+    radius_max: f64,
+    density: F,
+    seed: u64,
+) -> Vec<Vector2f64>
+where
+    F: Fn(Vector2f64) -> f64,
+{
+    let get_radius = |point| {
+        let density = density(point).clamp(0.0, 1.0);
+        radius_max - density * (radius_max - radius_min)
+    };
+
+    poisson::generate(bounds, radius_min, radius_max, get_radius, seed)
+}
+
+pub fn generate_diagram(sites: &[Vector2f64], bounds: &Bounds) -> Diagram {
+    diagram::generate(sites, bounds)
+}
+
+/// Generates a diagram on a torus: sites wrap across the bounds, so the diagram tiles seamlessly.
+pub fn generate_periodic_diagram(sites: &[Vector2f64], bounds: &Bounds) -> Diagram {
+    diagram::generate_periodic(sites, bounds)
+}
+
+/// Generates a power diagram in which sites with larger weights claim more area.
+pub fn generate_power_diagram(sites: &[WeightedSite], bounds: &Bounds) -> Diagram {
+    diagram::generate_power(sites, bounds)
+}
+
+pub fn rasterise_diagram(diagram: &Diagram, width: u32, height: u32) -> Raster {
+    raster::generate(diagram, width, height)
+}
+
+/// Converts a distance map of a raster into a height map usable by deep-rtin.
+pub fn get_height_map(
+    distances: &DistanceMap,
+    distance_max: f32,
+) -> ImageBuffer<Luma<u16>, Vec<u16>> {
+    raster::to_height_map(distances, distance_max)
+}
+
+/// Assembles plates from Voronoi cells and raises ridges and trenches along their boundaries.
+/// The height map is sampled at pixel centers like `rasterise_diagram`.
+pub fn generate_tectonic_map(
+    bounds: &Bounds,
+    settings: &TectonicSettings,
+    width: u32,
+    height: u32,
+    seed: u64,
+) -> TectonicMap {
+    tectonic::generate(bounds, settings, width, height, seed)
+}
+
+/// Cellular noise returning the distances to the nearest and second nearest feature point.
+/// Matches `worley_2d` of assets/shader/worley_noise_2d.wgsl.
+pub fn worley_2d(point: Vector2f32, jitter: f32) -> Vector2f32 {
+    worley::generate_2d(point, jitter)
+}
+
+/// Matches `worley` of assets/shader/worley_noise_3d.wgsl.
+pub fn worley_3d(point: Vector3f32, jitter: f32) -> Vector2f32 {
+    worley::generate_3d(point, jitter)
+}
@@ -0,0 +1,146 @@
+use std::f64::consts::TAU;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{bounds::Bounds, Vector2f64};
+
+// INFO: candidates tried around an active sample before it gets retired (Bridson uses 30)
+const CANDIDATE_COUNT: usize = 30;
+
+struct Sampler<'a> {
+    bounds: &'a Bounds,
+    cell_size: f64,
+    columns: usize,
+    rows: usize,
+    search_range: isize,
+    cells: Vec<Option<usize>>,
+    samples: Vec<(Vector2f64, f64)>,
+    active: Vec<usize>,
+}
+
+impl<'a> Sampler<'a> {
+    fn new(bounds: &'a Bounds, radius_min: f64, radius_max: f64) -> Self {
+        // INFO: with a diagonal of radius_min every cell holds at most one sample
+        let cell_size = radius_min / 2.0f64.sqrt();
+        let columns = (bounds.width() / cell_size).ceil() as usize;
+        let rows = (bounds.height() / cell_size).ceil() as usize;
+
+        Self {
+            bounds,
+            cell_size,
+            columns,
+            rows,
+            search_range: (radius_max / cell_size).ceil() as isize,
+            cells: vec![None; columns * rows],
+            samples: Vec::new(),
+            active: Vec::new(),
+        }
+    }
+
+    fn get_cell(&self, point: Vector2f64) -> (usize, usize) {
+        let column = ((point.x - self.bounds.min.x) / self.cell_size) as usize;
+        let row = ((point.y - self.bounds.min.y) / self.cell_size) as usize;
+
+        (column.min(self.columns - 1), row.min(self.rows - 1))
+    }
+
+    fn insert(&mut self, point: Vector2f64, radius: f64) {
+        let (column, row) = self.get_cell(point);
+        let index = self.samples.len();
+
+        self.cells[row * self.columns + column] = Some(index);
+        self.samples.push((point, radius));
+        self.active.push(index);
+    }
+
+    fn is_valid(&self, candidate: Vector2f64, candidate_radius: f64) -> bool {
+        let (column, row) = self.get_cell(candidate);
+        let (column, row) = (column as isize, row as isize);
+
+        for y in (row - self.search_range)..=(row + self.search_range) {
+            if y < 0 || y >= self.rows as isize {
+                continue;
+            }
+
+            for x in (column - self.search_range)..=(column + self.search_range) {
+                if x < 0 || x >= self.columns as isize {
+                    continue;
+                }
+
+                if let Some(index) = self.cells[y as usize * self.columns + x as usize] {
+                    let (sample, sample_radius) = self.samples[index];
+
+                    // INFO: both disks have to be respected to keep spacing symmetric
+                    let spacing = candidate_radius.max(sample_radius);
+                    if (sample - candidate).norm_squared() < spacing * spacing {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        true
+    }
+}
+
+// https://www.cs.ubc.ca/~rbridson/docs/bridson-siggraph07-poissondisk.pdf
+pub(crate) fn generate<F>(
+    bounds: &Bounds,
+    radius_min: f64,
+    radius_max: f64,
+    get_radius: F,
+    seed: u64,
+) -> Vec<Vector2f64>
+where
+    F: Fn(Vector2f64) -> f64,
+{
+    assert!(radius_min > 0.0, "Radius must be greater than zero!");
+    assert!(
+        radius_min <= radius_max,
+        "Minimum radius must not exceed maximum radius!"
+    );
+
+    let get_radius = |point| get_radius(point).clamp(radius_min, radius_max);
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut sampler = Sampler::new(bounds, radius_min, radius_max);
+
+    let first = Vector2f64::new(
+        rng.gen_range(bounds.min.x..bounds.max.x),
+        rng.gen_range(bounds.min.y..bounds.max.y),
+    );
+    sampler.insert(first, get_radius(first));
+
+    while !sampler.active.is_empty() {
+        let active_index = rng.gen_range(0..sampler.active.len());
+        let (origin, origin_radius) = sampler.samples[sampler.active[active_index]];
+
+        let mut found = false;
+        for _ in 0..CANDIDATE_COUNT {
+            let angle = rng.gen_range(0.0..TAU);
+            let distance = rng.gen_range(origin_radius..(2.0 * origin_radius));
+            let candidate = origin + Vector2f64::new(angle.cos(), angle.sin()) * distance;
+
+            if !bounds.contains(candidate) {
+                continue;
+            }
+
+            let candidate_radius = get_radius(candidate);
+            if sampler.is_valid(candidate, candidate_radius) {
+                sampler.insert(candidate, candidate_radius);
+                found = true;
+                break;
+            }
+        }
+
+        if !found {
+            sampler.active.swap_remove(active_index);
+        }
+    }
+
+    sampler
+        .samples
+        .into_iter()
+        .map(|(point, _)| point)
+        .collect()
+}
@@ -0,0 +1,25 @@
+use robust::Coord;
+
+use crate::Vector2f64;
+
+// INFO: adaptive precision predicates keep the triangulation valid for grid aligned and nearly
+// degenerate input, see https://www.cs.cmu.edu/~quake/robust.html
+
+/// Positive if `a`, `b` and `c` are in counterclockwise order, negative if clockwise and zero if
+/// they are collinear.
+pub(crate) fn orient2d(a: Vector2f64, b: Vector2f64, c: Vector2f64) -> f64 {
+    robust::orient2d(to_coord(a), to_coord(b), to_coord(c))
+}
+
+/// Positive if `d` lies inside the circumcircle of the counterclockwise triangle `a`, `b`, `c`,
+/// negative if outside and zero if all four points are cocircular.
+pub(crate) fn incircle(a: Vector2f64, b: Vector2f64, c: Vector2f64, d: Vector2f64) -> f64 {
+    robust::incircle(to_coord(a), to_coord(b), to_coord(c), to_coord(d))
+}
+
+fn to_coord(vector: Vector2f64) -> Coord<f64> {
+    Coord {
+        x: vector.x,
+        y: vector.y,
+    }
+}
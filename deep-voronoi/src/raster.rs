@@ -0,0 +1,93 @@
+use image::{ImageBuffer, Luma};
+
+use crate::{diagram::Diagram, Vector2f64};
+
+// INFO: nearest sites considered when searching the closest cell edge
+const EDGE_CANDIDATE_COUNT: usize = 8;
+
+pub type CellIdMap = ImageBuffer<Luma<u32>, Vec<u32>>;
+pub type DistanceMap = ImageBuffer<Luma<f32>, Vec<f32>>;
+
+/// Diagram sampled at pixel centers, pixel (0, 0) lies at the minimum of the bounds. Distances
+/// are euclidean, even for power diagrams.
+pub struct Raster {
+    pub cell_ids: CellIdMap,
+    /// Distance to the nearest site.
+    pub f1: DistanceMap,
+    /// Distance to the second nearest site.
+    pub f2: DistanceMap,
+    /// Distance to the closest edge of the cell containing the pixel.
+    pub edge_distance: DistanceMap,
+}
+
+pub(crate) fn generate(diagram: &Diagram, width: u32, height: u32) -> Raster {
+    let mut raster = Raster {
+        cell_ids: ImageBuffer::new(width, height),
+        f1: ImageBuffer::new(width, height),
+        f2: ImageBuffer::new(width, height),
+        edge_distance: ImageBuffer::new(width, height),
+    };
+
+    let bounds = &diagram.bounds;
+    let pixel_size = Vector2f64::new(
+        bounds.width() / width as f64,
+        bounds.height() / height as f64,
+    );
+
+    for y in 0..height {
+        for x in 0..width {
+            let point = bounds.min
+                + Vector2f64::new(
+                    (x as f64 + 0.5) * pixel_size.x,
+                    (y as f64 + 0.5) * pixel_size.y,
+                );
+
+            if let Some(cell_id) = diagram.get_cell_index(point) {
+                let nearest = diagram.get_nearest_sites(point, EDGE_CANDIDATE_COUNT);
+
+                let site = nearest[0];
+                let f1 = (site - point).norm();
+                let f2 = nearest
+                    .get(1)
+                    .map_or(f64::MAX, |other| (other - point).norm());
+
+                let edge_distance = nearest
+                    .iter()
+                    .skip(1)
+                    .map(|other| get_bisector_distance(site, *other, point))
+                    .fold(f64::MAX, f64::min);
+
+                raster.cell_ids.put_pixel(x, y, Luma([cell_id as u32]));
+                raster.f1.put_pixel(x, y, Luma([f1 as f32]));
+                raster.f2.put_pixel(x, y, Luma([f2 as f32]));
+                raster
+                    .edge_distance
+                    .put_pixel(x, y, Luma([edge_distance as f32]));
+            }
+        }
+    }
+
+    raster
+}
+
+fn get_bisector_distance(site: Vector2f64, other: Vector2f64, point: Vector2f64) -> f64 {
+    let distance = (other - site).norm();
+    if distance == 0.0 {
+        return f64::MAX;
+    }
+
+    ((other - point).norm_squared() - (site - point).norm_squared()) / (2.0 * distance)
+}
+
+/// Maps distances linearly onto 16 bit heights, distances beyond `distance_max` are clamped.
+pub(crate) fn to_height_map(
+    distances: &DistanceMap,
+    distance_max: f32,
+) -> ImageBuffer<Luma<u16>, Vec<u16>> {
+    ImageBuffer::from_fn(distances.width(), distances.height(), |x, y| {
+        let distance = distances.get_pixel(x, y).0[0];
+        let height = (distance / distance_max).clamp(0.0, 1.0);
+
+        Luma([(height * u16::MAX as f32) as u16])
+    })
+}
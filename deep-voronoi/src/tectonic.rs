@@ -0,0 +1,361 @@
+use std::f64::consts::{PI, TAU};
+
+use image::{ImageBuffer, Luma};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::{bounds::Bounds, diagram, poisson, raster::CellIdMap, Diagram, Vector2f64};
+
+/// Heights are fractions of the full height range, the resulting height map is clamped to it.
+#[derive(Clone, Copy, Debug)]
+pub struct TectonicSettings {
+    /// Spacing of the Voronoi cells plates are assembled from.
+    pub cell_radius: f64,
+    pub plate_count: usize,
+    /// Maximum drift speed of a plate, the unit only matters relative to the other plates.
+    pub drift_max: f64,
+    /// Distance from a plate boundary over which ridges, trenches and base heights fade out.
+    pub boundary_width: f64,
+    pub base_height_min: f64,
+    pub base_height_max: f64,
+    /// Height raised by divergent boundaries and on the overriding side of convergent ones.
+    pub ridge_height: f64,
+    /// Depth sunk on the subducting side of convergent boundaries.
+    pub trench_depth: f64,
+}
+
+impl Default for TectonicSettings {
+    fn default() -> Self {
+        Self {
+            cell_radius: 24.0,
+            plate_count: 8,
+            drift_max: 1.0,
+            boundary_width: 48.0,
+            base_height_min: 0.25,
+            base_height_max: 0.55,
+            ridge_height: 0.3,
+            trench_depth: 0.25,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Plate {
+    pub drift: Vector2f64,
+    pub base_height: f64,
+}
+
+pub struct TectonicMap {
+    pub diagram: Diagram,
+    pub plates: Vec<Plate>,
+    /// Plate of every cell of the diagram.
+    pub cell_plates: Vec<usize>,
+    pub cell_ids: CellIdMap,
+    pub plate_ids: CellIdMap,
+    pub height_map: ImageBuffer<Luma<u16>, Vec<u16>>,
+}
+
+// INFO: pixel on a plate boundary, the normal points from its own cell to the other cell
+#[derive(Clone, Copy)]
+struct BoundaryPixel {
+    position: Vector2f64,
+    plate: usize,
+    other_plate: usize,
+    normal: Vector2f64,
+}
+
+pub(crate) fn generate(
+    bounds: &Bounds,
+    settings: &TectonicSettings,
+    width: u32,
+    height: u32,
+    seed: u64,
+) -> TectonicMap {
+    assert!(settings.plate_count > 0, "At least one plate is required!");
+
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let sites = poisson::generate(
+        bounds,
+        settings.cell_radius,
+        settings.cell_radius,
+        |_| settings.cell_radius,
+        rng.gen(),
+    );
+    let diagram = diagram::generate(&sites, bounds);
+
+    let plates: Vec<Plate> = (0..settings.plate_count.min(sites.len()))
+        .map(|_| {
+            let angle = rng.gen_range(0.0..TAU);
+            let speed = rng.gen_range(0.0..=settings.drift_max);
+
+            Plate {
+                drift: Vector2f64::new(angle.cos(), angle.sin()) * speed,
+                base_height: rng.gen_range(settings.base_height_min..=settings.base_height_max),
+            }
+        })
+        .collect();
+    let cell_plates = assign_plates(&diagram, plates.len(), &mut rng);
+
+    let pixel_size = Vector2f64::new(
+        bounds.width() / width as f64,
+        bounds.height() / height as f64,
+    );
+    let get_position = |x: u32, y: u32| {
+        bounds.min
+            + Vector2f64::new(
+                (x as f64 + 0.5) * pixel_size.x,
+                (y as f64 + 0.5) * pixel_size.y,
+            )
+    };
+
+    let cell_ids: CellIdMap = ImageBuffer::from_fn(width, height, |x, y| {
+        let cell = diagram.get_cell_index(get_position(x, y)).unwrap_or(0);
+        Luma([cell as u32])
+    });
+    let plate_ids: CellIdMap = ImageBuffer::from_fn(width, height, |x, y| {
+        Luma([cell_plates[cell_ids.get_pixel(x, y).0[0] as usize] as u32])
+    });
+
+    let mut boundary = Vec::new();
+    let mut nearest = vec![None; (width * height) as usize];
+    for y in 0..height {
+        for x in 0..width {
+            let cell = cell_ids.get_pixel(x, y).0[0] as usize;
+
+            let other_cell = [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                .into_iter()
+                .filter_map(|(dx, dy)| get_pixel(&cell_ids, x as i64 + dx, y as i64 + dy))
+                .map(|other| other as usize)
+                .find(|other| cell_plates[*other] != cell_plates[cell]);
+
+            if let Some(other_cell) = other_cell {
+                nearest[(y * width + x) as usize] = Some(boundary.len());
+                boundary.push(BoundaryPixel {
+                    position: get_position(x, y),
+                    plate: cell_plates[cell],
+                    other_plate: cell_plates[other_cell],
+                    normal: (diagram.cells[other_cell].site - diagram.cells[cell].site).normalize(),
+                });
+            }
+        }
+    }
+
+    propagate_nearest(&mut nearest, &boundary, width, height, get_position);
+
+    let mut heights: Vec<f64> = (0..width * height)
+        .map(|index| {
+            let (x, y) = (index % width, index / width);
+            let plate = plate_ids.get_pixel(x, y).0[0] as usize;
+
+            match nearest[index as usize] {
+                Some(boundary_index) => get_height(
+                    settings,
+                    &plates,
+                    plate,
+                    get_position(x, y),
+                    &boundary[boundary_index],
+                ),
+                None => plates[plate].base_height,
+            }
+        })
+        .collect();
+
+    // INFO: the nearest boundary pixel jumps between cells with different normals, blurring
+    // twice approximates a gaussian and hides the resulting creases
+    let radius = (settings.boundary_width / 4.0 / pixel_size.x.max(pixel_size.y)) as usize;
+    for _ in 0..2 {
+        blur(&mut heights, width as usize, height as usize, radius, 1);
+        blur(
+            &mut heights,
+            height as usize,
+            width as usize,
+            radius,
+            width as usize,
+        );
+    }
+
+    let height_map = ImageBuffer::from_fn(width, height, |x, y| {
+        let height = heights[(y * width + x) as usize];
+        Luma([(height.clamp(0.0, 1.0) * u16::MAX as f64) as u16])
+    });
+
+    TectonicMap {
+        diagram,
+        plates,
+        cell_plates,
+        cell_ids,
+        plate_ids,
+        height_map,
+    }
+}
+
+/// Grows all plates at once from random cells, taking one random frontier cell per step.
+fn assign_plates(diagram: &Diagram, plate_count: usize, rng: &mut StdRng) -> Vec<usize> {
+    let cell_count = diagram.cells.len();
+    let mut cell_plates = vec![usize::MAX; cell_count];
+
+    let mut seeds: Vec<usize> = (0..cell_count).collect();
+    seeds.shuffle(rng);
+
+    let mut frontier = Vec::new();
+    for (plate, cell) in seeds.into_iter().take(plate_count).enumerate() {
+        cell_plates[cell] = plate;
+        frontier.push(cell);
+    }
+
+    while !frontier.is_empty() {
+        let position = rng.gen_range(0..frontier.len());
+        let cell = frontier.swap_remove(position);
+
+        for neighbour in diagram.cells[cell].neighbours.iter() {
+            if cell_plates[*neighbour] == usize::MAX {
+                cell_plates[*neighbour] = cell_plates[cell];
+                frontier.push(*neighbour);
+            }
+        }
+    }
+
+    // INFO: duplicate sites have no neighbours and are never reached
+    cell_plates
+        .into_iter()
+        .map(|plate| if plate == usize::MAX { 0 } else { plate })
+        .collect()
+}
+
+fn get_pixel(map: &CellIdMap, x: i64, y: i64) -> Option<u32> {
+    if x < 0 || y < 0 || x >= map.width() as i64 || y >= map.height() as i64 {
+        return None;
+    }
+
+    Some(map.get_pixel(x as u32, y as u32).0[0])
+}
+
+// https://en.wikipedia.org/wiki/Distance_transform
+/// Two chamfer passes passing the nearest boundary pixel on to the neighbouring pixels.
+fn propagate_nearest<F>(
+    nearest: &mut [Option<usize>],
+    boundary: &[BoundaryPixel],
+    width: u32,
+    height: u32,
+    get_position: F,
+) where
+    F: Fn(u32, u32) -> Vector2f64,
+{
+    let (width, height) = (width as i64, height as i64);
+    let forward = [(-1, -1), (0, -1), (1, -1), (-1, 0)];
+    let backward = [(1, 1), (0, 1), (-1, 1), (1, 0)];
+
+    let mut visit = |x: i64, y: i64, offsets: &[(i64, i64)]| {
+        let position = get_position(x as u32, y as u32);
+        let index = (y * width + x) as usize;
+
+        for (dx, dy) in offsets {
+            let (other_x, other_y) = (x + dx, y + dy);
+            if other_x < 0 || other_y < 0 || other_x >= width || other_y >= height {
+                continue;
+            }
+
+            if let Some(candidate) = nearest[(other_y * width + other_x) as usize] {
+                let distance = (boundary[candidate].position - position).norm_squared();
+                let is_closer = match nearest[index] {
+                    Some(current) => {
+                        distance < (boundary[current].position - position).norm_squared()
+                    }
+                    None => true,
+                };
+
+                if is_closer {
+                    nearest[index] = Some(candidate);
+                }
+            }
+        }
+    };
+
+    for y in 0..height {
+        for x in 0..width {
+            visit(x, y, &forward);
+        }
+    }
+
+    for y in (0..height).rev() {
+        for x in (0..width).rev() {
+            visit(x, y, &backward);
+        }
+    }
+}
+
+/// Box blur along lines of `length` values that are `stride` apart, lines start at multiples of
+/// `length * stride` or at offsets below `stride`. Samples beyond the ends are clamped.
+fn blur(values: &mut [f64], length: usize, line_count: usize, radius: usize, stride: usize) {
+    if radius == 0 {
+        return;
+    }
+
+    let mut line = vec![0.0; length];
+    for line_index in 0..line_count {
+        let start = if stride == 1 {
+            line_index * length
+        } else {
+            line_index
+        };
+        let get_value = |position: isize| {
+            let position = position.clamp(0, length as isize - 1) as usize;
+            values[start + position * stride]
+        };
+
+        let radius = radius as isize;
+        let mut sum: f64 = (-radius..=radius).map(get_value).sum();
+        for (position, value) in line.iter_mut().enumerate() {
+            *value = sum / (2 * radius + 1) as f64;
+
+            let position = position as isize;
+            sum += get_value(position + radius + 1) - get_value(position - radius);
+        }
+
+        for (position, value) in line.iter().enumerate() {
+            values[start + position * stride] = *value;
+        }
+    }
+}
+
+fn get_height(
+    settings: &TectonicSettings,
+    plates: &[Plate],
+    plate: usize,
+    position: Vector2f64,
+    boundary: &BoundaryPixel,
+) -> f64 {
+    let (other_plate, normal) = if boundary.plate == plate {
+        (boundary.other_plate, boundary.normal)
+    } else {
+        (boundary.plate, -boundary.normal)
+    };
+
+    let t = ((boundary.position - position).norm() / settings.boundary_width).min(1.0);
+    let falloff = 1.0 - t * t * (3.0 - 2.0 * t);
+
+    // INFO: blends base heights towards the boundary so transform boundaries stay continuous
+    let own = plates[plate];
+    let other = plates[other_plate];
+    let base_height = own.base_height + (other.base_height - own.base_height) * falloff / 2.0;
+
+    // INFO: positive when the plates move towards each other
+    let convergence =
+        (own.drift - other.drift).dot(&normal) / (2.0 * settings.drift_max).max(f64::EPSILON);
+
+    // INFO: the lower plate subducts below the other one, ties go to the lower index
+    let is_subducting = (own.base_height, plate) < (other.base_height, other_plate);
+
+    // INFO: divergent ridges peak at the boundary, trenches and arcs of convergent boundaries
+    // peak inside their plate so both sides meet at the blended base height
+    let bump = (t * PI).sin();
+    let offset = if convergence < 0.0 {
+        -convergence * settings.ridge_height * falloff
+    } else if is_subducting {
+        -convergence * settings.trench_depth * bump
+    } else {
+        convergence * settings.ridge_height * bump
+    };
+
+    base_height + offset
+}
@@ -0,0 +1,136 @@
+// INFO: the constants are kept as written in the shaders
+#![allow(clippy::excessive_precision)]
+
+use crate::{Vector2f32, Vector3f32};
+
+// INFO: ports of assets/shader/worley_noise_2d.wgsl and assets/shader/worley_noise_3d.wgsl, the
+// order of operations is kept to stay consistent with the gpu results
+const K: f32 = 0.142857142857; // 1/7
+const KO: f32 = 0.428571428571; // 1/2-k/2
+const K2: f32 = 0.020408163265306; // 1/(7*7)
+const KZ: f32 = 0.166666666667; // 1/6
+const KZO: f32 = 0.416666666667; // 1/2-1/6*2
+
+fn permute(x: Vector3f32) -> Vector3f32 {
+    x.map(|x| (((x * 34.0) + 10.0) * x) % 289.0)
+}
+
+fn fract(x: f32) -> f32 {
+    x - x.floor()
+}
+
+pub(crate) fn generate_2d(v: Vector2f32, jitter: f32) -> Vector2f32 {
+    let pi = v.map(|x| x.floor() % 289.0);
+    let pf = v.map(fract);
+
+    let offset_i = Vector3f32::new(-1.0, 0.0, 1.0);
+    let offset_f = Vector3f32::new(-0.5, 0.5, 1.5);
+
+    let px = permute(offset_i.add_scalar(pi.x));
+
+    let mut d = [Vector3f32::zeros(); 3];
+    for (column, offset_x) in [0.5, -0.5, -1.5].into_iter().enumerate() {
+        let p = permute(offset_i.add_scalar(px[column] + pi.y));
+        let ox = p.map(|x| fract(x * K) - KO);
+        let oy = p.map(|x| ((x * K).floor() % 7.0) * K - KO);
+        let dx = ox.map(|x| pf.x + offset_x + jitter * x);
+        let dy = offset_f.zip_map(&oy, |f, y| pf.y - f + jitter * y);
+
+        d[column] = dx.component_mul(&dx) + dy.component_mul(&dy);
+    }
+
+    let [mut d1, mut d2, d3] = d;
+
+    // Sort out the two smallest distances (F1, F2)
+    let d1a = d1.inf(&d2);
+    d2 = d1.sup(&d2); // Swap to keep candidates for F2
+    d2 = d2.inf(&d3); // neither F1 nor F2 are now in d3
+    d1 = d1a.inf(&d2); // F1 is now in d1
+    d2 = d1a.sup(&d2); // Swap to keep candidates for F2
+    if d1.x > d1.y {
+        d1.swap_rows(0, 1);
+    }
+    if d1.x > d1.z {
+        // F1 is in d1.x
+        d1.swap_rows(0, 2);
+    }
+    d1.y = d1.y.min(d2.y); // F2 is now not in d2.yz
+    d1.z = d1.z.min(d2.z);
+    d1.y = d1.y.min(d1.z); // nor in d1.z
+    d1.y = d1.y.min(d2.x); // F2 is in d1.y, we're done.
+
+    Vector2f32::new(d1.x.sqrt(), d1.y.sqrt()) // F1, F2
+}
+
+pub(crate) fn generate_3d(v: Vector3f32, jitter: f32) -> Vector2f32 {
+    let pi = v.map(|x| x.floor() % 289.0);
+    let pf = v.map(|x| fract(x) - 0.5);
+
+    let offset = Vector3f32::new(1.0, 0.0, -1.0);
+    let pfx = offset.add_scalar(pf.x);
+    let pfy = offset.add_scalar(pf.y);
+    let pfz = offset.add_scalar(pf.z);
+
+    let p = permute(Vector3f32::new(-1.0, 0.0, 1.0).add_scalar(pi.x));
+    let p1 = permute(p.add_scalar(pi.y).add_scalar(-1.0));
+    let p2 = permute(p.add_scalar(pi.y));
+    let p3 = permute(p.add_scalar(pi.y).add_scalar(1.0));
+
+    // INFO: d[y][z] matches d11 to d33 of the shader
+    let mut d = [[Vector3f32::zeros(); 3]; 3];
+    for (y, py) in [p1, p2, p3].into_iter().enumerate() {
+        for (z, offset_z) in [-1.0, 0.0, 1.0].into_iter().enumerate() {
+            let pyz = permute(py.add_scalar(pi.z).add_scalar(offset_z));
+
+            let ox = pyz.map(|x| fract(x * K) - KO);
+            let oy = pyz.map(|x| ((x * K).floor() % 7.0) * K - KO);
+            let oz = pyz.map(|x| (x * K2).floor() * KZ - KZO); // pyz < 289 guaranteed
+
+            let dx = pfx + ox * jitter;
+            let dy = oy.map(|x| pfy[y] + jitter * x);
+            let dz = oz.map(|x| pfz[z] + jitter * x);
+
+            d[y][z] = dx.component_mul(&dx) + dy.component_mul(&dy) + dz.component_mul(&dz);
+        }
+    }
+
+    let [[mut d11, mut d12, mut d13], [mut d21, mut d22, mut d23], [mut d31, mut d32, mut d33]] = d;
+
+    // Sort out the two smallest distances (F1, F2)
+    let d1a = d11.inf(&d12);
+    d12 = d11.sup(&d12);
+    d11 = d1a.inf(&d13); // Smallest now not in d12 or d13
+    d13 = d1a.sup(&d13);
+    d12 = d12.inf(&d13); // 2nd smallest now not in d13
+    let d2a = d21.inf(&d22);
+    d22 = d21.sup(&d22);
+    d21 = d2a.inf(&d23); // Smallest now not in d22 or d23
+    d23 = d2a.sup(&d23);
+    d22 = d22.inf(&d23); // 2nd smallest now not in d23
+    let d3a = d31.inf(&d32);
+    d32 = d31.sup(&d32);
+    d31 = d3a.inf(&d33); // Smallest now not in d32 or d33
+    d33 = d3a.sup(&d33);
+    d32 = d32.inf(&d33); // 2nd smallest now not in d33
+    let da = d11.inf(&d21);
+    d21 = d11.sup(&d21);
+    d11 = da.inf(&d31); // Smallest now in d11
+    d31 = da.sup(&d31); // 2nd smallest now not in d31
+    if d11.x > d11.y {
+        d11.swap_rows(0, 1);
+    }
+    if d11.x > d11.z {
+        // d11.x now smallest
+        d11.swap_rows(0, 2);
+    }
+    d12 = d12.inf(&d21); // 2nd smallest now not in d21
+    d12 = d12.inf(&d22); // nor in d22
+    d12 = d12.inf(&d31); // nor in d31
+    d12 = d12.inf(&d32); // nor in d32
+    d11.y = d11.y.min(d12.x);
+    d11.z = d11.z.min(d12.y); // nor in d12.yz
+    d11.y = d11.y.min(d12.z); // Only two more to go
+    d11.y = d11.y.min(d11.z); // Done! (phew!)
+
+    Vector2f32::new(d11.x.sqrt(), d11.y.sqrt()) // F1, F2
+}
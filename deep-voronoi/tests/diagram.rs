@@ -0,0 +1,235 @@
+use deep_voronoi::{Bounds, Diagram, DynamicDiagram, Vector2f64, WeightedSite};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn get_area(polygon: &[Vector2f64]) -> f64 {
+    let doubled: f64 = (0..polygon.len())
+        .map(|index| {
+            let start = polygon[index];
+            let end = polygon[(index + 1) % polygon.len()];
+            start.x * end.y - end.x * start.y
+        })
+        .sum();
+
+    doubled / 2.0
+}
+
+fn get_random_sites(rng: &mut StdRng, bounds: &Bounds, count: usize) -> Vec<Vector2f64> {
+    (0..count)
+        .map(|_| {
+            Vector2f64::new(
+                rng.gen_range(bounds.min.x..bounds.max.x),
+                rng.gen_range(bounds.min.y..bounds.max.y),
+            )
+        })
+        .collect()
+}
+
+fn assert_partition(diagram: &Diagram) {
+    let area: f64 = diagram
+        .cells
+        .iter()
+        .map(|cell| get_area(&cell.polygon))
+        .sum();
+    let expected = diagram.bounds.width() * diagram.bounds.height();
+    assert!(
+        (area - expected).abs() < expected * 1e-9,
+        "{} != {}",
+        area,
+        expected
+    );
+
+    for (index, cell) in diagram.cells.iter().enumerate() {
+        assert!(cell.polygon.is_empty() || get_area(&cell.polygon) > 0.0);
+
+        for neighbour in cell.neighbours.iter() {
+            assert!(diagram.cells[*neighbour].neighbours.contains(&index));
+        }
+    }
+}
+
+#[test]
+fn random_diagrams_partition_bounds() {
+    let bounds = Bounds::new(Vector2f64::new(-50.0, 0.0), Vector2f64::new(50.0, 20.0));
+
+    for seed in 0..30 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let count = rng.gen_range(1..150);
+        let sites = get_random_sites(&mut rng, &bounds, count);
+
+        assert_partition(&deep_voronoi::generate_diagram(&sites, &bounds));
+        assert_partition(&deep_voronoi::generate_periodic_diagram(&sites, &bounds));
+    }
+}
+
+#[test]
+fn pixel_centre_diagrams_partition_bounds() {
+    let bounds = Bounds::new(Vector2f64::new(0.0, 0.0), Vector2f64::new(16.0, 16.0));
+    let sites: Vec<Vector2f64> = (0..16 * 16)
+        .map(|index| Vector2f64::new((index % 16) as f64 + 0.5, (index / 16) as f64 + 0.5))
+        .collect();
+
+    let diagram = deep_voronoi::generate_diagram(&sites, &bounds);
+    assert_partition(&diagram);
+    assert!(diagram
+        .cells
+        .iter()
+        .all(|cell| (get_area(&cell.polygon) - 1.0).abs() < 1e-9));
+
+    let periodic = deep_voronoi::generate_periodic_diagram(&sites, &bounds);
+    assert_partition(&periodic);
+    assert!(periodic.cells.iter().all(|cell| cell.neighbours.len() == 4));
+}
+
+#[test]
+fn diagrams_keep_neighbours_at_every_scale() {
+    let bounds = Bounds::new(Vector2f64::new(0.0, 0.0), Vector2f64::new(100.0, 100.0));
+    let mut rng = StdRng::seed_from_u64(5);
+    let sites = get_random_sites(&mut rng, &bounds, 100);
+    let diagram = deep_voronoi::generate_diagram(&sites, &bounds);
+
+    // INFO: powers of two scale the coordinates without rounding
+    for scale in [2f64.powi(-40), 2f64.powi(40)] {
+        let scaled_bounds = Bounds::new(bounds.min * scale, bounds.max * scale);
+        let scaled_sites: Vec<Vector2f64> = sites.iter().map(|site| site * scale).collect();
+        let scaled = deep_voronoi::generate_diagram(&scaled_sites, &scaled_bounds);
+
+        for (cell, scaled_cell) in diagram.cells.iter().zip(scaled.cells.iter()) {
+            assert_eq!(cell.neighbours, scaled_cell.neighbours);
+        }
+    }
+}
+
+#[test]
+fn power_diagrams_partition_bounds() {
+    let bounds = Bounds::new(Vector2f64::new(0.0, 0.0), Vector2f64::new(100.0, 100.0));
+
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let count = rng.gen_range(1..80);
+        let sites: Vec<WeightedSite> = get_random_sites(&mut rng, &bounds, count)
+            .into_iter()
+            .map(|position| WeightedSite {
+                position,
+                weight: rng.gen_range(0.0..400.0),
+            })
+            .collect();
+
+        let diagram = deep_voronoi::generate_power_diagram(&sites, &bounds);
+        assert_partition(&diagram);
+
+        for _ in 0..100 {
+            let point = get_random_sites(&mut rng, &bounds, 1)[0];
+            let power = |site: &WeightedSite| (site.position - point).norm_squared() - site.weight;
+            let expected = (0..count)
+                .min_by(|a, b| power(&sites[*a]).total_cmp(&power(&sites[*b])))
+                .unwrap();
+
+            assert_eq!(diagram.get_cell_index(point), Some(expected));
+        }
+    }
+}
+
+#[test]
+fn dense_power_diagrams_partition_bounds() {
+    let bounds = Bounds::new(Vector2f64::new(0.0, 0.0), Vector2f64::new(100.0, 100.0));
+    let mut rng = StdRng::seed_from_u64(3);
+
+    // INFO: few heavy sites reach across many light ones
+    let sites: Vec<WeightedSite> = get_random_sites(&mut rng, &bounds, 600)
+        .into_iter()
+        .map(|position| WeightedSite {
+            position,
+            weight: if rng.gen_bool(0.02) {
+                rng.gen_range(0.0..2000.0)
+            } else {
+                rng.gen_range(0.0..4.0)
+            },
+        })
+        .collect();
+
+    assert_partition(&deep_voronoi::generate_power_diagram(&sites, &bounds));
+}
+
+#[test]
+fn coincident_sites_resolve_to_non_empty_cell() {
+    let bounds = Bounds::new(Vector2f64::new(0.0, 0.0), Vector2f64::new(10.0, 10.0));
+    let position = Vector2f64::new(4.0, 4.0);
+    let sites = [
+        Vector2f64::new(8.0, 8.0),
+        position,
+        position,
+        Vector2f64::new(1.0, 7.0),
+        position,
+    ];
+
+    let diagram = deep_voronoi::generate_diagram(&sites, &bounds);
+    assert_partition(&diagram);
+    let index = diagram.get_cell_index(position).unwrap();
+    assert!(!diagram.cells[index].polygon.is_empty());
+
+    let weighted: Vec<WeightedSite> = sites
+        .iter()
+        .map(|position| WeightedSite {
+            position: *position,
+            weight: 1.0,
+        })
+        .collect();
+    let diagram = deep_voronoi::generate_power_diagram(&weighted, &bounds);
+    assert_partition(&diagram);
+    let index = diagram.get_cell_index(position).unwrap();
+    assert!(!diagram.cells[index].polygon.is_empty());
+}
+
+#[test]
+fn dynamic_diagram_matches_rebuilt_diagram() {
+    let bounds = Bounds::new(Vector2f64::new(0.0, 0.0), Vector2f64::new(40.0, 30.0));
+    let mut rng = StdRng::seed_from_u64(11);
+
+    let mut dynamic = DynamicDiagram::new(&bounds);
+    let mut ids = Vec::new();
+    for site in get_random_sites(&mut rng, &bounds, 200) {
+        ids.push(dynamic.insert(site).unwrap());
+
+        if rng.gen_bool(0.25) {
+            let id = ids.swap_remove(rng.gen_range(0..ids.len()));
+            assert!(dynamic.remove(id));
+            assert!(dynamic.get_cell(id).is_none());
+        }
+    }
+
+    let changed = dynamic.take_changed_cells();
+    assert!(ids.iter().all(|id| changed.contains(id)));
+    assert!(dynamic.take_changed_cells().is_empty());
+
+    let sites: Vec<Vector2f64> = ids
+        .iter()
+        .map(|id| dynamic.get_cell(*id).unwrap().site)
+        .collect();
+    let diagram = deep_voronoi::generate_diagram(&sites, &bounds);
+    assert_eq!(dynamic.len(), ids.len());
+
+    for (index, id) in ids.iter().enumerate() {
+        let cell = dynamic.get_cell(*id).unwrap();
+        let expected = &diagram.cells[index];
+        assert!((get_area(&cell.polygon) - get_area(&expected.polygon)).abs() < 1e-9);
+
+        let mut neighbours: Vec<usize> = cell.neighbours.clone();
+        let mut expected_neighbours: Vec<usize> =
+            expected.neighbours.iter().map(|n| ids[*n]).collect();
+        neighbours.sort_unstable();
+        expected_neighbours.sort_unstable();
+        assert_eq!(neighbours, expected_neighbours);
+
+        let point = cell.site + Vector2f64::new(1e-6, 0.0);
+        assert_eq!(dynamic.get_cell_id(point), Some(*id));
+    }
+
+    // INFO: a removal only reshapes the cells around the removed site
+    let id = ids[0];
+    let neighbours = dynamic.get_cell(id).unwrap().neighbours.clone();
+    dynamic.remove(id);
+    let changed = dynamic.take_changed_cells();
+    assert!(changed
+        .iter()
+        .all(|other| *other == id || neighbours.contains(other)));
+}
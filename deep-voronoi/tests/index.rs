@@ -0,0 +1,125 @@
+use deep_voronoi::{SiteIndex, Vector2f64};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn get_random_sites(rng: &mut StdRng, count: usize) -> Vec<Vector2f64> {
+    let mut sites: Vec<Vector2f64> = (0..count)
+        .map(|_| {
+            // INFO: snapped to a coarse grid, so sites share axes and distances
+            Vector2f64::new(
+                rng.gen_range(-20..20) as f64 * 0.5,
+                rng.gen_range(-20..20) as f64 * 0.5,
+            )
+        })
+        .collect();
+
+    // INFO: exact duplicates of existing sites
+    for _ in 0..count / 4 {
+        let site = sites[rng.gen_range(0..sites.len())];
+        sites.push(site);
+    }
+
+    sites
+}
+
+fn get_random_point(rng: &mut StdRng) -> Vector2f64 {
+    Vector2f64::new(rng.gen_range(-12.0..12.0), rng.gen_range(-12.0..12.0))
+}
+
+fn get_distances(sites: &[Vector2f64], point: Vector2f64, indices: &[usize]) -> Vec<f64> {
+    indices
+        .iter()
+        .map(|index| (sites[*index] - point).norm_squared())
+        .collect()
+}
+
+fn get_sorted_distances(sites: &[Vector2f64], point: Vector2f64) -> Vec<f64> {
+    let mut distances: Vec<f64> = sites
+        .iter()
+        .map(|site| (site - point).norm_squared())
+        .collect();
+    distances.sort_by(f64::total_cmp);
+
+    distances
+}
+
+#[test]
+fn nearest_matches_brute_force() {
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let count = rng.gen_range(1..200);
+        let sites = get_random_sites(&mut rng, count);
+        let index = SiteIndex::new(&sites);
+
+        for _ in 0..50 {
+            let point = get_random_point(&mut rng);
+            let nearest = index.nearest(point).unwrap();
+
+            assert_eq!(
+                (sites[nearest] - point).norm_squared(),
+                get_sorted_distances(&sites, point)[0]
+            );
+        }
+    }
+
+    assert_eq!(SiteIndex::new(&[]).nearest(Vector2f64::zeros()), None);
+}
+
+#[test]
+fn k_nearest_matches_brute_force() {
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let count = rng.gen_range(1..200);
+        let sites = get_random_sites(&mut rng, count);
+        let index = SiteIndex::new(&sites);
+
+        for k in [0, 1, 2, 7, sites.len(), sites.len() + 5] {
+            let point = get_random_point(&mut rng);
+            let nearest = index.k_nearest(point, k);
+
+            let mut unique = nearest.clone();
+            unique.sort_unstable();
+            unique.dedup();
+            assert_eq!(unique.len(), nearest.len());
+
+            let expected: Vec<f64> = get_sorted_distances(&sites, point)
+                .into_iter()
+                .take(k)
+                .collect();
+            assert_eq!(get_distances(&sites, point, &nearest), expected);
+        }
+    }
+
+    assert!(SiteIndex::new(&[])
+        .k_nearest(Vector2f64::zeros(), 3)
+        .is_empty());
+}
+
+#[test]
+fn within_radius_matches_brute_force() {
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let count = rng.gen_range(1..200);
+        let sites = get_random_sites(&mut rng, count);
+        let index = SiteIndex::new(&sites);
+
+        for radius in [0.0, 0.5, 1.5, 4.0, 30.0] {
+            let point = get_random_point(&mut rng);
+
+            let mut found = index.within_radius(point, radius);
+            found.sort_unstable();
+
+            let expected: Vec<usize> = (0..sites.len())
+                .filter(|index| (sites[*index] - point).norm_squared() <= radius * radius)
+                .collect();
+            assert_eq!(found, expected);
+        }
+
+        // INFO: a query on a site itself has to find it and all of its duplicates
+        let site = sites[0];
+        let mut found = index.within_radius(site, 0.0);
+        found.sort_unstable();
+
+        let expected: Vec<usize> = (0..sites.len()).filter(|i| sites[*i] == site).collect();
+        assert_eq!(found, expected);
+    }
+}
@@ -0,0 +1,87 @@
+use deep_voronoi::{Bounds, Vector2f64};
+
+fn get_bounds() -> Bounds {
+    Bounds::new(Vector2f64::new(-20.0, 10.0), Vector2f64::new(30.0, 40.0))
+}
+
+fn assert_spacing<F>(samples: &[Vector2f64], get_radius: F)
+where
+    F: Fn(Vector2f64) -> f64,
+{
+    for (index, sample) in samples.iter().enumerate() {
+        for other in samples[index + 1..].iter() {
+            let spacing = get_radius(*sample).max(get_radius(*other));
+            let distance = (sample - other).norm();
+
+            assert!(
+                distance >= spacing,
+                "{} and {} are {} apart, below {}",
+                sample,
+                other,
+                distance,
+                spacing
+            );
+        }
+    }
+}
+
+#[test]
+fn samples_keep_radius_and_stay_in_bounds() {
+    let bounds = get_bounds();
+
+    for seed in 0..4 {
+        let radius = 1.0 + seed as f64 * 0.5;
+        let samples = deep_voronoi::sample_poisson_disk(&bounds, radius, seed);
+
+        assert!(samples.len() > 1);
+        assert!(samples.iter().all(|sample| bounds.contains(*sample)));
+        assert_spacing(&samples, |_| radius);
+    }
+}
+
+#[test]
+fn density_samples_keep_local_radius_and_stay_in_bounds() {
+    let bounds = get_bounds();
+    let (radius_min, radius_max) = (1.0, 6.0);
+
+    let density = |point: Vector2f64| (point.x - bounds.min.x) / bounds.width();
+    let get_radius =
+        |point| radius_max - density(point).clamp(0.0, 1.0) * (radius_max - radius_min);
+
+    for seed in 0..4 {
+        let samples = deep_voronoi::sample_poisson_disk_by_density(
+            &bounds, radius_min, radius_max, density, seed,
+        );
+
+        assert!(samples.len() > 1);
+        assert!(samples.iter().all(|sample| bounds.contains(*sample)));
+        assert_spacing(&samples, get_radius);
+
+        // INFO: the dense half has to hold more samples than the sparse one
+        let center = bounds.min.x + bounds.width() / 2.0;
+        let dense = samples.iter().filter(|sample| sample.x >= center).count();
+        assert!(dense > samples.len() - dense);
+    }
+}
+
+#[test]
+fn same_seed_gives_same_samples() {
+    let bounds = get_bounds();
+    let density = |point: Vector2f64| (point.y - bounds.min.y) / bounds.height();
+
+    for seed in 0..3 {
+        assert_eq!(
+            deep_voronoi::sample_poisson_disk(&bounds, 2.0, seed),
+            deep_voronoi::sample_poisson_disk(&bounds, 2.0, seed)
+        );
+        assert_eq!(
+            deep_voronoi::sample_poisson_disk_by_density(&bounds, 1.0, 4.0, density, seed),
+            deep_voronoi::sample_poisson_disk_by_density(&bounds, 1.0, 4.0, density, seed)
+        );
+    }
+
+    assert_ne!(
+        deep_voronoi::sample_poisson_disk(&bounds, 2.0, 0),
+        deep_voronoi::sample_poisson_disk(&bounds, 2.0, 1)
+    );
+}
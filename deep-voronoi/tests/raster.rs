@@ -0,0 +1,76 @@
+use deep_voronoi::{Bounds, Vector2f64};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const WIDTH: u32 = 64;
+const HEIGHT: u32 = 48;
+
+fn get_bounds() -> Bounds {
+    Bounds::new(Vector2f64::new(-32.0, 0.0), Vector2f64::new(32.0, 48.0))
+}
+
+/// Sites placed on pixel centers, so the raster samples every site exactly.
+fn get_pixel_sites(rng: &mut StdRng, bounds: &Bounds, count: usize) -> Vec<(u32, u32)> {
+    let mut pixels: Vec<(u32, u32)> = (0..count)
+        .map(|_| (rng.gen_range(0..WIDTH), rng.gen_range(0..HEIGHT)))
+        .collect();
+    pixels.sort_unstable();
+    pixels.dedup();
+
+    assert_eq!(bounds.width(), WIDTH as f64);
+    assert_eq!(bounds.height(), HEIGHT as f64);
+
+    pixels
+}
+
+#[test]
+fn f1_does_not_exceed_f2() {
+    let bounds = get_bounds();
+
+    for seed in 0..5 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let count = rng.gen_range(2..60);
+        let sites: Vec<Vector2f64> = (0..count)
+            .map(|_| {
+                Vector2f64::new(
+                    rng.gen_range(bounds.min.x..bounds.max.x),
+                    rng.gen_range(bounds.min.y..bounds.max.y),
+                )
+            })
+            .collect();
+
+        let diagram = deep_voronoi::generate_diagram(&sites, &bounds);
+        let raster = deep_voronoi::rasterise_diagram(&diagram, WIDTH, HEIGHT);
+
+        for (x, y, f1) in raster.f1.enumerate_pixels() {
+            let f2 = raster.f2.get_pixel(x, y).0[0];
+            let edge_distance = raster.edge_distance.get_pixel(x, y).0[0];
+
+            assert!(f1.0[0] >= 0.0);
+            assert!(f1.0[0] <= f2, "{} > {} at {}, {}", f1.0[0], f2, x, y);
+            assert!(edge_distance >= 0.0);
+        }
+    }
+}
+
+#[test]
+fn f1_is_zero_at_sites() {
+    let bounds = get_bounds();
+
+    for seed in 0..5 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let pixels = get_pixel_sites(&mut rng, &bounds, 40);
+        let sites: Vec<Vector2f64> = pixels
+            .iter()
+            .map(|(x, y)| bounds.min + Vector2f64::new(*x as f64 + 0.5, *y as f64 + 0.5))
+            .collect();
+
+        let diagram = deep_voronoi::generate_diagram(&sites, &bounds);
+        let raster = deep_voronoi::rasterise_diagram(&diagram, WIDTH, HEIGHT);
+
+        for (index, (x, y)) in pixels.iter().enumerate() {
+            assert_eq!(raster.f1.get_pixel(*x, *y).0[0], 0.0);
+            assert!(raster.f2.get_pixel(*x, *y).0[0] >= 1.0);
+            assert_eq!(raster.cell_ids.get_pixel(*x, *y).0[0], index as u32);
+        }
+    }
+}
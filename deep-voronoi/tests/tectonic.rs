@@ -0,0 +1,16 @@
+use deep_voronoi::{Bounds, TectonicSettings, Vector2f64};
+
+#[test]
+fn same_seed_gives_same_tectonic_map() {
+    let bounds = Bounds::new(Vector2f64::new(0.0, 0.0), Vector2f64::new(256.0, 256.0));
+    let settings = TectonicSettings::default();
+
+    for seed in 0..3 {
+        let first = deep_voronoi::generate_tectonic_map(&bounds, &settings, 128, 128, seed);
+        let second = deep_voronoi::generate_tectonic_map(&bounds, &settings, 128, 128, seed);
+
+        assert_eq!(first.cell_plates, second.cell_plates);
+        assert_eq!(first.plate_ids, second.plate_ids);
+        assert_eq!(first.height_map, second.height_map);
+    }
+}
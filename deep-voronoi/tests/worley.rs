@@ -0,0 +1,84 @@
+use std::f32::consts::FRAC_1_SQRT_2;
+
+use deep_voronoi::{Vector2f32, Vector3f32};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+// INFO: tolerance for the f32 rounding differences between the cpu and gpu
+const EPSILON: f32 = 1e-4;
+
+fn assert_close(actual: Vector2f32, expected: [f32; 2]) {
+    assert!(
+        (actual.x - expected[0]).abs() < EPSILON && (actual.y - expected[1]).abs() < EPSILON,
+        "{:?} != {:?}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn f1_does_not_exceed_f2() {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..1000 {
+        let jitter = rng.gen_range(0.0..1.0);
+        let point_2d = Vector2f32::new(rng.gen_range(-300.0..300.0), rng.gen_range(-300.0..300.0));
+        let point_3d = Vector3f32::new(
+            rng.gen_range(-300.0..300.0),
+            rng.gen_range(-300.0..300.0),
+            rng.gen_range(-300.0..300.0),
+        );
+
+        let f_2d = deep_voronoi::worley_2d(point_2d, jitter);
+        let f_3d = deep_voronoi::worley_3d(point_3d, jitter);
+
+        assert!(0.0 <= f_2d.x && f_2d.x <= f_2d.y, "{:?}", f_2d);
+        assert!(0.0 <= f_3d.x && f_3d.x <= f_3d.y, "{:?}", f_3d);
+    }
+}
+
+#[test]
+fn f1_is_zero_at_feature_points() {
+    // INFO: without jitter every feature point sits at the center of its cell
+    for x in -3..3 {
+        for y in -3..3 {
+            let center_2d = Vector2f32::new(x as f32 + 0.5, y as f32 + 0.5);
+            assert_close(deep_voronoi::worley_2d(center_2d, 0.0), [0.0, 1.0]);
+
+            let center_3d = Vector3f32::new(x as f32 + 0.5, y as f32 + 0.5, 7.5);
+            assert_close(deep_voronoi::worley_3d(center_3d, 0.0), [0.0, 1.0]);
+        }
+    }
+}
+
+#[test]
+fn values_match_shaders() {
+    // INFO: evaluated with the operations of assets/shader/worley_noise_2d.wgsl
+    let expected_2d = [
+        ([0.0, 0.0], 1.0, [0.416497, FRAC_1_SQRT_2]),
+        ([0.25, 0.75], 1.0, [0.507595, 0.701674]),
+        ([3.7, -2.2], 0.8, [0.682881, 0.725765]),
+        ([-12.5, 40.125], 0.5, [0.315925, 0.558161]),
+    ];
+
+    for ([x, y], jitter, expected) in expected_2d {
+        assert_close(
+            deep_voronoi::worley_2d(Vector2f32::new(x, y), jitter),
+            expected,
+        );
+    }
+
+    // INFO: evaluated with the operations of assets/shader/worley_noise_3d.wgsl
+    let expected_3d = [
+        ([0.0, 0.0, 0.0], 1.0, [0.682736, 0.712000]),
+        ([0.25, 0.75, 0.5], 1.0, [0.680449, 0.682113]),
+        ([3.7, -2.2, 1.1], 0.8, [0.535815, 0.588341]),
+        ([-12.5, 40.125, 7.75], 0.5, [0.328300, 0.857515]),
+    ];
+
+    for ([x, y, z], jitter, expected) in expected_3d {
+        assert_close(
+            deep_voronoi::worley_3d(Vector3f32::new(x, y, z), jitter),
+            expected,
+        );
+    }
+}
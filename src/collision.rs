@@ -0,0 +1,69 @@
+use bevy_rapier3d::prelude::{CollisionGroups, Group, QueryFilter};
+
+/// Named collision groups, every spawned collider belongs to one of them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CollisionLayer {
+    Terrain,
+    Vessels,
+    Props,
+    Sensors,
+    Projectiles,
+}
+
+impl CollisionLayer {
+    pub fn get_group(&self) -> Group {
+        match self {
+            CollisionLayer::Terrain => Group::GROUP_1,
+            CollisionLayer::Vessels => Group::GROUP_2,
+            CollisionLayer::Props => Group::GROUP_3,
+            CollisionLayer::Sensors => Group::GROUP_4,
+            CollisionLayer::Projectiles => Group::GROUP_5,
+        }
+    }
+
+    /// Layers colliders of this layer interact with, the table is symmetric.
+    pub fn get_filter(&self) -> Group {
+        let groups = match self {
+            CollisionLayer::Terrain => [
+                CollisionLayer::Vessels,
+                CollisionLayer::Props,
+                CollisionLayer::Projectiles,
+            ]
+            .as_slice(),
+            CollisionLayer::Vessels => [
+                CollisionLayer::Terrain,
+                CollisionLayer::Vessels,
+                CollisionLayer::Props,
+                CollisionLayer::Sensors,
+                CollisionLayer::Projectiles,
+            ]
+            .as_slice(),
+            CollisionLayer::Props => [
+                CollisionLayer::Terrain,
+                CollisionLayer::Vessels,
+                CollisionLayer::Projectiles,
+            ]
+            .as_slice(),
+            CollisionLayer::Sensors => [CollisionLayer::Vessels].as_slice(),
+            CollisionLayer::Projectiles => [
+                CollisionLayer::Terrain,
+                CollisionLayer::Vessels,
+                CollisionLayer::Props,
+            ]
+            .as_slice(),
+        };
+
+        groups
+            .iter()
+            .fold(Group::NONE, |filter, layer| filter | layer.get_group())
+    }
+
+    pub fn get_collision_groups(&self) -> CollisionGroups {
+        CollisionGroups::new(self.get_group(), self.get_filter())
+    }
+
+    /// Scene query filter hitting only colliders of this layer.
+    pub fn get_query_filter(&self) -> QueryFilter<'static> {
+        QueryFilter::new().groups(CollisionGroups::new(Group::ALL, self.get_group()))
+    }
+}
@@ -1,11 +1,15 @@
 use bevy::prelude::*;
 use bevy_editor_pls::prelude::*;
 use bevy_rapier3d::prelude::*;
+use ocean::OceanPlugin;
 use render::CustomRenderPlugin;
 use submarine::SubmarinePlugin;
 use terrain::TerrainPlugin;
 
+mod collision;
 mod color;
+mod ocean;
+mod physics;
 mod render;
 mod submarine;
 mod terrain;
@@ -24,6 +28,7 @@ fn main() {
         .add_plugin(RapierPhysicsPlugin::<NoUserData>::default())
         .add_plugin(CustomRenderPlugin::default())
         .add_plugin(TerrainPlugin::default())
+        .add_plugin(OceanPlugin::default())
         .add_plugin(SubmarinePlugin::default())
         .run();
 }
@@ -0,0 +1,322 @@
+use std::f32::consts::TAU;
+
+use bevy::{pbr::NotShadowCaster, prelude::*};
+use bevy_atmosphere::prelude::AtmosphereCamera;
+use bevy_rapier3d::prelude::{
+    ExternalImpulse, RapierConfiguration, ReadMassProperties, RigidBody, Velocity,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    physics,
+    submarine::drag::DragComponent,
+    terrain::{
+        biome::{Biome, BiomeMap},
+        TerrainHeightMap, WorldSeed,
+    },
+};
+
+use super::OceanWaves;
+
+const FLOW_WAVES: usize = 6;
+// INFO: pixels between the height samples of the trench slope, smooths the stream direction
+const STREAM_SMOOTHING: i64 = 16;
+// INFO: height above the trench floor over which the streams fade out
+const STREAM_HEIGHT: f32 = 24.0;
+
+#[derive(Resource, Clone, Debug)]
+pub struct CurrentSettings {
+    /// Average speed of the open water flow in m/s.
+    pub flow_speed: f32,
+    /// Average size of the flow eddies.
+    pub flow_scale: f32,
+    /// Speed of the streams along the trench floors in m/s.
+    pub stream_speed: f32,
+    pub particle_count: usize,
+    /// Half side length of the cube around the camera filled with particles.
+    pub particle_range: f32,
+}
+
+impl Default for CurrentSettings {
+    fn default() -> Self {
+        Self {
+            flow_speed: 0.6,
+            flow_scale: 96.0,
+            stream_speed: 2.5,
+            particle_count: 400,
+            particle_range: 32.0,
+        }
+    }
+}
+
+struct FlowWave {
+    direction: Vec2,
+    wavenumber: f32,
+    phase: f32,
+    angular_speed: f32,
+    speed: f32,
+}
+
+struct StreamCell {
+    velocity: Vec2,
+    floor: f32,
+}
+
+/// Horizontal water velocity field, a divergence free flow noise overlaid with streams
+/// following the trenches.
+#[derive(Resource)]
+pub struct OceanCurrents {
+    flow_waves: Vec<FlowWave>,
+    streams: Vec<StreamCell>,
+    width: u32,
+    height: u32,
+    bounds: (Vec2, Vec2),
+}
+
+impl OceanCurrents {
+    pub fn generate(
+        terrain_height_map: &TerrainHeightMap,
+        biome_map: &BiomeMap,
+        settings: &CurrentSettings,
+        seed: u64,
+    ) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let flow_waves = (0..FLOW_WAVES)
+            .map(|_| {
+                let angle = rng.gen_range(0.0..TAU);
+
+                FlowWave {
+                    direction: Vec2::new(angle.cos(), angle.sin()),
+                    wavenumber: TAU / (settings.flow_scale * rng.gen_range(0.5..1.5)),
+                    phase: rng.gen_range(0.0..TAU),
+                    angular_speed: rng.gen_range(-0.05..0.05),
+                    speed: settings.flow_speed * rng.gen_range(0.5..1.5)
+                        / (FLOW_WAVES as f32).sqrt(),
+                }
+            })
+            .collect();
+
+        let height_map = &terrain_height_map.height_map;
+        let (width, height) = height_map.dimensions();
+        let bounds = terrain_height_map.get_bounds();
+        let cell_size = (bounds.1 - bounds.0) / Vec2::new(width as f32, height as f32);
+
+        let get_height = |x: i64, y: i64| {
+            let x = x.clamp(0, width as i64 - 1) as u32;
+            let y = y.clamp(0, height as i64 - 1) as u32;
+
+            height_map.get_pixel(x, y).0[0] as f32 / u16::MAX as f32
+        };
+
+        let get_trench = |x: i64, y: i64| {
+            let x = x.clamp(0, width as i64 - 1) as u32;
+            let y = y.clamp(0, height as i64 - 1) as u32;
+
+            (biome_map.get_biome(x, y) == Biome::Trench) as u8 as f32
+        };
+
+        let mut streams = Vec::with_capacity((width * height) as usize);
+        for y in 0..height as i64 {
+            for x in 0..width as i64 {
+                let position = bounds.0 + (Vec2::new(x as f32, y as f32) + 0.5) * cell_size;
+                let floor = terrain_height_map.get_height(position.x, position.y);
+
+                // INFO: the streams follow the isolines of the trench, deeper parts to the left
+                let slope = Vec2::new(
+                    get_height(x + STREAM_SMOOTHING, y) - get_height(x - STREAM_SMOOTHING, y),
+                    get_height(x, y + STREAM_SMOOTHING) - get_height(x, y - STREAM_SMOOTHING),
+                );
+                // INFO: averaging the neighbours softens the stream border at the trench edge
+                let weight = (get_trench(x, y)
+                    + get_trench(x + STREAM_SMOOTHING, y)
+                    + get_trench(x - STREAM_SMOOTHING, y)
+                    + get_trench(x, y + STREAM_SMOOTHING)
+                    + get_trench(x, y - STREAM_SMOOTHING))
+                    / 5.0;
+
+                streams.push(StreamCell {
+                    velocity: slope.perp().normalize_or_zero() * settings.stream_speed * weight,
+                    floor,
+                });
+            }
+        }
+
+        Self {
+            flow_waves,
+            streams,
+            width,
+            height,
+            bounds,
+        }
+    }
+
+    /// Returns the water velocity at `position`, at `time` seconds.
+    pub fn get_velocity(&self, position: Vec3, time: f32) -> Vec3 {
+        // INFO: every wave is a stream function, its curl is a flow perpendicular to the wave
+        let flow = self.flow_waves.iter().fold(Vec2::ZERO, |flow, wave| {
+            let angle = wave.wavenumber * wave.direction.dot(position.xz())
+                + wave.phase
+                + wave.angular_speed * time;
+
+            flow + wave.direction.perp() * wave.speed * angle.cos()
+        });
+
+        let size = Vec2::new(self.width as f32, self.height as f32);
+        let local = (position.xz() - self.bounds.0) / (self.bounds.1 - self.bounds.0) * size;
+        let stream = if local.cmpge(Vec2::ZERO).all() && local.cmplt(size).all() {
+            let cell = &self.streams[local.y as usize * self.width as usize + local.x as usize];
+            let weight = 1.0 - smoothstep(0.0, STREAM_HEIGHT, position.y - cell.floor);
+
+            cell.velocity * weight
+        } else {
+            Vec2::ZERO
+        };
+
+        let velocity = flow + stream;
+        Vec3::new(velocity.x, 0.0, velocity.y)
+    }
+}
+
+/// Overrides the current coupling of a body without a hull drag model. The coupling in
+/// 1/s is the rate at which the body takes over the water velocity.
+#[derive(Clone, Component, Debug)]
+pub struct CurrentDragComponent {
+    pub coupling: f32,
+}
+
+impl Default for CurrentDragComponent {
+    fn default() -> Self {
+        Self { coupling: 0.5 }
+    }
+}
+
+#[derive(Component)]
+pub struct CurrentParticleComponent {}
+
+pub fn setup_currents(
+    mut commands: Commands,
+    terrain_height_map: Res<TerrainHeightMap>,
+    biome_map: Res<BiomeMap>,
+    settings: Res<CurrentSettings>,
+    world_seed: Res<WorldSeed>,
+) {
+    commands.insert_resource(OceanCurrents::generate(
+        &terrain_height_map,
+        &biome_map,
+        &settings,
+        world_seed.derive([0, 0, 0x0c0f_f10e]),
+    ));
+}
+
+pub fn spawn_particles(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    settings: Res<CurrentSettings>,
+) {
+    let mesh = meshes.add(
+        shape::Icosphere {
+            radius: 0.05,
+            subdivisions: 0,
+        }
+        .into(),
+    );
+    let material = materials.add(StandardMaterial {
+        base_color: Color::rgba(0.8, 0.9, 1.0, 0.6),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+
+    let mut rng = rand::thread_rng();
+    let range = settings.particle_range;
+    for _ in 0..settings.particle_count {
+        commands.spawn((
+            PbrBundle {
+                mesh: mesh.clone(),
+                material: material.clone(),
+                transform: Transform::from_xyz(
+                    rng.gen_range(-range..range),
+                    rng.gen_range(-range..range),
+                    rng.gen_range(-range..range),
+                ),
+                ..default()
+            },
+            NotShadowCaster,
+            CurrentParticleComponent {},
+        ));
+    }
+}
+
+/// Drifts the particles with the current and wraps them into the cube around the camera.
+pub fn update_particles(
+    time: Res<Time>,
+    currents: Res<OceanCurrents>,
+    ocean_waves: Res<OceanWaves>,
+    settings: Res<CurrentSettings>,
+    camera_query: Query<&GlobalTransform, With<AtmosphereCamera>>,
+    mut query: Query<(&mut Transform, &mut Visibility), With<CurrentParticleComponent>>,
+) {
+    if let Ok(camera_transform) = camera_query.get_single() {
+        let center = camera_transform.translation();
+        let range = settings.particle_range;
+        let elapsed = time.elapsed_seconds_wrapped();
+
+        for (mut transform, mut visibility) in query.iter_mut() {
+            let position = transform.translation
+                + currents.get_velocity(transform.translation, elapsed) * time.delta_seconds();
+            let offset = position - center + range;
+            let offset = offset - (offset / (range * 2.0)).floor() * range * 2.0 - range;
+
+            transform.translation = center + offset;
+            *visibility = if transform.translation.y < ocean_waves.sea_level {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+        }
+    }
+}
+
+/// Drags every dynamic body along with the current, the submarine hull has its own drag model.
+pub fn apply_current_drag(
+    rapier_configuration: Res<RapierConfiguration>,
+    time: Res<Time>,
+    currents: Res<OceanCurrents>,
+    mut query: Query<
+        (
+            &RigidBody,
+            Option<&CurrentDragComponent>,
+            &Transform,
+            &Velocity,
+            &ReadMassProperties,
+            &mut ExternalImpulse,
+        ),
+        Without<DragComponent>,
+    >,
+) {
+    let elapsed = time.elapsed_seconds_wrapped();
+    let dt = physics::get_step_delta_seconds(&rapier_configuration, &time);
+    let default_drag = CurrentDragComponent::default();
+
+    for (rigid_body, current_drag, transform, velocity, mass_properties, mut impulse) in
+        query.iter_mut()
+    {
+        if *rigid_body != RigidBody::Dynamic {
+            continue;
+        }
+
+        let current_drag = current_drag.unwrap_or(&default_drag);
+        let current = currents.get_velocity(transform.translation, elapsed);
+        let difference = current - velocity.linvel;
+
+        // INFO: the coupling may at most take over the whole velocity difference in one step
+        impulse.impulse +=
+            difference * mass_properties.0.mass * (current_drag.coupling * dt).min(1.0);
+    }
+}
+
+fn smoothstep(edge_min: f32, edge_max: f32, value: f32) -> f32 {
+    let t = ((value - edge_min) / (edge_max - edge_min)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
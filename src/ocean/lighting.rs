@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+use bevy_atmosphere::prelude::AtmosphereCamera;
+
+use crate::terrain::sky::{CelestialLightComponent, WorldClock};
+
+use super::OceanWaves;
+
+// INFO: depth in m at which each color channel drops to 1/e, the water swallows red first
+const ABSORPTION_DEPTH: Vec3 = Vec3::new(3.0, 10.0, 15.0);
+// INFO: fraction of the daylight the water keeps at night, the moon is too weak to rely on it
+const NIGHT_BRIGHTNESS: f32 = 0.05;
+const AMBIENT_BRIGHTNESS: f32 = 0.05;
+const FOG_COLOR: Vec3 = Vec3::new(0.0, 0.36, 0.45);
+const FOG_EXTINCTION_COLOR: Vec3 = Vec3::new(0.35, 0.5, 0.66);
+const FOG_VISIBILITY_SURFACE: f32 = 256.0;
+const FOG_VISIBILITY_ABYSS: f32 = 24.0;
+
+/// Fraction of each color channel of the surface light reaching the depth.
+fn get_transmission(depth: f32) -> Vec3 {
+    (-depth.max(0.0) / ABSORPTION_DEPTH).exp()
+}
+
+/// Brightness of the water at the surface, between NIGHT_BRIGHTNESS and 1.0 at noon.
+fn get_daylight(clock: &WorldClock) -> f32 {
+    let elevation = clock.get_sun_direction().y.max(0.0);
+    NIGHT_BRIGHTNESS + (1.0 - NIGHT_BRIGHTNESS) * elevation.sqrt()
+}
+
+/// Depth of the camera below the wave surface right above it.
+fn get_camera_depth(ocean_waves: &OceanWaves, time: &Time, camera_transform: &Transform) -> f32 {
+    let position = camera_transform.translation;
+    let wave_height =
+        ocean_waves.get_height(position.x, position.z, time.elapsed_seconds_wrapped());
+
+    wave_height - position.y
+}
+
+fn to_color(channels: Vec3) -> Color {
+    Color::rgb(channels.x, channels.y, channels.z)
+}
+
+/// Dims and tints the sun and moon by the water above the camera.
+pub fn attenuate_celestial_lights(
+    ocean_waves: Res<OceanWaves>,
+    time: Res<Time>,
+    camera_query: Query<&Transform, With<AtmosphereCamera>>,
+    mut light_query: Query<(&CelestialLightComponent, &mut DirectionalLight)>,
+) {
+    if let Ok(camera_transform) = camera_query.get_single() {
+        let depth = get_camera_depth(&ocean_waves, &time, camera_transform);
+        let transmission = get_transmission(depth);
+
+        for (light, mut directional) in light_query.iter_mut() {
+            let [red, green, blue, _] = light.surface_color.as_rgba_f32();
+
+            directional.color = to_color(Vec3::new(red, green, blue) * transmission);
+            directional.illuminance = light.surface_illuminance * transmission.max_element();
+        }
+    }
+}
+
+/// Darkens the fog and ambient light with depth and time of day, the abyss stays black.
+pub fn update_underwater_fog(
+    clock: Res<WorldClock>,
+    ocean_waves: Res<OceanWaves>,
+    time: Res<Time>,
+    mut ambient_light: ResMut<AmbientLight>,
+    mut camera_query: Query<(&Transform, &mut FogSettings), With<AtmosphereCamera>>,
+) {
+    if let Ok((camera_transform, mut fog)) = camera_query.get_single_mut() {
+        let depth = get_camera_depth(&ocean_waves, &time, camera_transform);
+        let transmission = get_transmission(depth);
+        let daylight = get_daylight(&clock);
+
+        let visibility = FOG_VISIBILITY_ABYSS
+            + (FOG_VISIBILITY_SURFACE - FOG_VISIBILITY_ABYSS) * transmission.y * daylight;
+
+        fog.color = to_color(FOG_COLOR * transmission * daylight);
+        fog.falloff = FogFalloff::from_visibility_color(visibility, to_color(FOG_EXTINCTION_COLOR));
+
+        ambient_light.brightness = AMBIENT_BRIGHTNESS * transmission.max_element() * daylight;
+    }
+}
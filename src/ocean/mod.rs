@@ -0,0 +1,204 @@
+use std::f32::consts::PI;
+
+use bevy::{
+    pbr::NotShadowCaster,
+    prelude::{shape::Plane, *},
+};
+use bevy_atmosphere::prelude::AtmosphereCamera;
+
+use crate::render::ocean::{OceanMaterial, OCEAN_WAVES_MAX};
+
+use self::current::CurrentSettings;
+
+pub mod current;
+mod lighting;
+
+const GRAVITY: f32 = 9.81;
+// INFO: the plane follows the camera in steps of one vertex, so the waves do not swim
+const OCEAN_SIZE: f32 = 1024.0;
+const OCEAN_SUBDIVISIONS: u32 = 511;
+
+#[derive(Default)]
+pub struct OceanPlugin {}
+
+impl Plugin for OceanPlugin {
+    fn build(&self, app: &mut App) {
+        app //
+            .init_resource::<OceanWaves>()
+            .init_resource::<CurrentSettings>()
+            .add_system(spawn_ocean.on_startup())
+            .add_systems(
+                (current::setup_currents, current::spawn_particles)
+                    .on_startup()
+                    .in_base_set(StartupSet::PostStartup),
+            )
+            .add_system(follow_camera)
+            .add_system(update_ocean_material)
+            .add_systems((
+                lighting::attenuate_celestial_lights,
+                lighting::update_underwater_fog,
+            ))
+            .add_systems((current::update_particles, current::apply_current_drag));
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GerstnerWave {
+    pub direction: Vec2,
+    pub wavelength: f32,
+    /// Between 0.0 and 1.0, the sum of all waves has to stay below 1.0 or the crests loop.
+    pub steepness: f32,
+}
+
+/// Waves of the ocean surface, shared by the shader and the physics.
+#[derive(Resource, Clone, Debug)]
+pub struct OceanWaves {
+    pub sea_level: f32,
+    pub waves: Vec<GerstnerWave>,
+}
+
+impl Default for OceanWaves {
+    fn default() -> Self {
+        Self {
+            sea_level: 24.0,
+            waves: vec![
+                GerstnerWave {
+                    direction: Vec2::new(1.0, 0.3),
+                    wavelength: 60.0,
+                    steepness: 0.25,
+                },
+                GerstnerWave {
+                    direction: Vec2::new(-0.4, 1.0),
+                    wavelength: 31.0,
+                    steepness: 0.2,
+                },
+                GerstnerWave {
+                    direction: Vec2::new(0.7, -0.6),
+                    wavelength: 18.0,
+                    steepness: 0.15,
+                },
+                GerstnerWave {
+                    direction: Vec2::new(0.2, 0.9),
+                    wavelength: 12.0,
+                    steepness: 0.1,
+                },
+            ],
+        }
+    }
+}
+
+impl OceanWaves {
+    /// Returns the offset of the undisturbed surface point at `position`, at `time` seconds.
+    pub fn get_displacement(&self, position: Vec2, time: f32) -> Vec3 {
+        self.waves
+            .iter()
+            .take(OCEAN_WAVES_MAX)
+            .filter(|wave| wave.steepness > 0.0)
+            .fold(Vec3::ZERO, |offset, wave| {
+                let k = 2.0 * PI / wave.wavelength;
+                let c = (GRAVITY / k).sqrt();
+                let direction = wave.direction.normalize();
+                let f = k * (direction.dot(position) - c * time);
+                let a = wave.steepness / k;
+
+                offset
+                    + Vec3::new(
+                        direction.x * a * f.cos(),
+                        a * f.sin(),
+                        direction.y * a * f.cos(),
+                    )
+            })
+    }
+
+    /// Returns the world space height of the surface at `x`, `z`, at `time` seconds.
+    pub fn get_height(&self, x: f32, z: f32, time: f32) -> f32 {
+        // INFO: the waves move the surface horizontally too, a few fixed point iterations find
+        // the undisturbed point which is displaced onto x, z
+        let target = Vec2::new(x, z);
+        let mut position = target;
+        for _ in 0..4 {
+            position = target - self.get_displacement(position, time).xz();
+        }
+
+        self.sea_level + self.get_displacement(position, time).y
+    }
+
+    fn get_uniforms(&self) -> [Vec4; OCEAN_WAVES_MAX] {
+        let mut uniforms = [Vec4::new(1.0, 0.0, 0.0, 1.0); OCEAN_WAVES_MAX];
+        for (uniform, wave) in uniforms.iter_mut().zip(self.waves.iter()) {
+            *uniform = Vec4::new(
+                wave.direction.x,
+                wave.direction.y,
+                wave.steepness,
+                wave.wavelength,
+            );
+        }
+
+        uniforms
+    }
+}
+
+#[derive(Component)]
+pub struct OceanComponent {}
+
+fn spawn_ocean(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut ocean_materials: ResMut<Assets<OceanMaterial>>,
+    ocean_waves: Res<OceanWaves>,
+) {
+    commands.spawn((
+        MaterialMeshBundle {
+            mesh: meshes.add(
+                Plane {
+                    size: OCEAN_SIZE,
+                    subdivisions: OCEAN_SUBDIVISIONS,
+                }
+                .into(),
+            ),
+            material: ocean_materials.add(OceanMaterial {
+                color: Color::rgba(0.0, 0.25, 0.35, 0.85),
+                foam_color: Color::rgba(0.85, 0.9, 0.95, 0.95),
+                waves: ocean_waves.get_uniforms(),
+                alpha_mode: AlphaMode::Blend,
+            }),
+            transform: Transform::from_xyz(0.0, ocean_waves.sea_level, 0.0),
+            ..default()
+        },
+        NotShadowCaster,
+        OceanComponent {},
+    ));
+}
+
+fn follow_camera(
+    camera_query: Query<&GlobalTransform, With<AtmosphereCamera>>,
+    mut query: Query<&mut Transform, With<OceanComponent>>,
+) {
+    let spacing = OCEAN_SIZE / (OCEAN_SUBDIVISIONS + 1) as f32;
+
+    if let (Ok(camera_transform), Ok(mut transform)) =
+        (camera_query.get_single(), query.get_single_mut())
+    {
+        let position = (camera_transform.translation().xz() / spacing).round() * spacing;
+        transform.translation.x = position.x;
+        transform.translation.z = position.y;
+    }
+}
+
+fn update_ocean_material(
+    ocean_waves: Res<OceanWaves>,
+    mut ocean_materials: ResMut<Assets<OceanMaterial>>,
+    mut query: Query<(&mut Transform, &Handle<OceanMaterial>), With<OceanComponent>>,
+) {
+    if !ocean_waves.is_changed() {
+        return;
+    }
+
+    for (mut transform, handle) in query.iter_mut() {
+        transform.translation.y = ocean_waves.sea_level;
+
+        if let Some(material) = ocean_materials.get_mut(handle) {
+            material.waves = ocean_waves.get_uniforms();
+        }
+    }
+}
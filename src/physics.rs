@@ -0,0 +1,14 @@
+use bevy::prelude::Time;
+use bevy_rapier3d::prelude::{RapierConfiguration, TimestepMode};
+
+/// Returns the seconds the next physics step integrates. Rapier caps or fixes its timestep, so
+/// forces applied as `ExternalImpulse` have to be scaled by this instead of the frame time.
+pub fn get_step_delta_seconds(rapier_configuration: &RapierConfiguration, time: &Time) -> f32 {
+    match rapier_configuration.timestep_mode {
+        TimestepMode::Fixed { dt, .. } => dt,
+        TimestepMode::Variable {
+            max_dt, time_scale, ..
+        } => (time.delta_seconds() * time_scale).min(max_dt),
+        TimestepMode::Interpolated { dt, time_scale, .. } => dt * time_scale,
+    }
+}
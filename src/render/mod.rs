@@ -1,12 +1,18 @@
 use bevy::{asset::HandleId, prelude::*};
 
-use self::{force_field::ForceFieldMaterial, line::LineMaterial};
+use self::{
+    force_field::ForceFieldMaterial, line::LineMaterial, ocean::OceanMaterial,
+    terrain::TerrainMaterial,
+};
 
 pub mod force_field;
 pub mod line;
+pub mod ocean;
+pub mod terrain;
 
 pub const FRESNEL: &str = include_str!("../../assets/shader/fresnel.wgsl");
 pub const SIMPLEX_NOISE_3D: &str = include_str!("../../assets/shader/simplex_noise_3d.wgsl");
+pub const WORLEY_NOISE_2D: &str = include_str!("../../assets/shader/worley_noise_2d.wgsl");
 pub const WORLEY_NOISE_3D: &str = include_str!("../../assets/shader/worley_noise_3d.wgsl");
 
 #[derive(Default)]
@@ -23,7 +29,13 @@ impl Plugin for CustomRenderPlugin {
             .add_plugin(MaterialPlugin::<LineMaterial> {
                 prepass_enabled: false,
                 ..default()
-            });
+            })
+            .add_plugin(MaterialPlugin::<OceanMaterial> {
+                prepass_enabled: false,
+                ..default()
+            })
+            // INFO: the prepass stays enabled as the force field intersects with the terrain depth
+            .add_plugin(MaterialPlugin::<TerrainMaterial>::default());
     }
 }
 
@@ -32,6 +44,7 @@ impl Plugin for CustomRenderPlugin {
 pub struct CustomShaderHandles {
     fresnel: HandleId,
     simplex_noise_3d: HandleId,
+    worley_noise_2d: HandleId,
     worley_noise_3d: HandleId,
 }
 
@@ -42,6 +55,7 @@ impl FromWorld for CustomShaderHandles {
         CustomShaderHandles {
             fresnel: load_shader(&mut shaders, "fresnel", FRESNEL),
             simplex_noise_3d: load_shader(&mut shaders, "simplex_noise_3d", SIMPLEX_NOISE_3D),
+            worley_noise_2d: load_shader(&mut shaders, "worley_noise_2d", WORLEY_NOISE_2D),
             worley_noise_3d: load_shader(&mut shaders, "worley_noise_3d", WORLEY_NOISE_3D),
         }
     }
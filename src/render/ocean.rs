@@ -0,0 +1,54 @@
+use bevy::{
+    pbr::{MaterialPipeline, MaterialPipelineKey},
+    prelude::{AlphaMode, Color, Material, Vec4},
+    reflect::TypeUuid,
+    render::{
+        mesh::MeshVertexBufferLayout,
+        render_resource::{
+            AsBindGroup, RenderPipelineDescriptor, ShaderRef, SpecializedMeshPipelineError,
+        },
+    },
+};
+
+pub const OCEAN_WAVES_MAX: usize = 4;
+
+/// Water surface displaced by Gerstner waves in the vertex shader, the crests fade to
+/// `foam_color`. Rendered from both sides, so it is visible from below the surface.
+#[derive(AsBindGroup, TypeUuid, Debug, Clone)]
+#[uuid = "c1d7a2f4-6e3b-4b8a-9f25-0d4e8a7b3c61"]
+pub struct OceanMaterial {
+    #[uniform(0)]
+    pub color: Color,
+    #[uniform(0)]
+    pub foam_color: Color,
+    /// Waves as direction x, direction z, steepness and wavelength, unused waves have a
+    /// steepness of zero.
+    #[uniform(0)]
+    pub waves: [Vec4; OCEAN_WAVES_MAX],
+    pub alpha_mode: AlphaMode,
+}
+
+impl Material for OceanMaterial {
+    fn vertex_shader() -> ShaderRef {
+        "material/ocean.wgsl".into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        "material/ocean.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        self.alpha_mode
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayout,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        descriptor.primitive.cull_mode = None;
+
+        Ok(())
+    }
+}
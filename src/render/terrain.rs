@@ -0,0 +1,87 @@
+use bevy::{
+    pbr::{MaterialPipeline, MaterialPipelineKey},
+    prelude::{Color, Material, Mesh},
+    reflect::TypeUuid,
+    render::{
+        mesh::{MeshVertexAttribute, MeshVertexBufferLayout},
+        render_resource::{
+            AsBindGroup, RenderPipelineDescriptor, ShaderRef, SpecializedMeshPipelineError,
+            VertexFormat,
+        },
+    },
+};
+
+/// Weights of the sand flat, rocky slope, vent field and trench biome per vertex.
+pub const ATTRIBUTE_BIOME_WEIGHTS: MeshVertexAttribute =
+    MeshVertexAttribute::new("BiomeWeights", 718_245_903, VertexFormat::Float32x4);
+
+/// Terrain colored by its biome vertex colors, steep slopes fade to `cliff_color` and deep
+/// terrain is shaded darker. Simplex noise breaks up both blends.
+#[derive(AsBindGroup, TypeUuid, Debug, Clone)]
+#[uuid = "4b6a3c1e-95d2-4f3e-8a0c-2f1d7e6b9c55"]
+pub struct TerrainMaterial {
+    #[uniform(0)]
+    pub cliff_color: Color,
+    /// Slope as 1 - normal.y at which cliffs start and fully take over.
+    #[uniform(0)]
+    pub cliff_start: f32,
+    #[uniform(0)]
+    pub cliff_end: f32,
+    /// World heights between which the terrain brightens from `depth_shade` to full color.
+    #[uniform(0)]
+    pub depth_min: f32,
+    #[uniform(0)]
+    pub depth_max: f32,
+    #[uniform(0)]
+    pub depth_shade: f32,
+    /// Frequency of the simplex noise breaking up the colors and the cliff border.
+    #[uniform(0)]
+    pub noise_scale: f32,
+}
+
+impl Default for TerrainMaterial {
+    fn default() -> Self {
+        Self {
+            cliff_color: Color::hsl(263.0, 0.25, 0.3),
+            cliff_start: 0.3,
+            cliff_end: 0.5,
+            depth_min: -48.0,
+            depth_max: 16.0,
+            depth_shade: 0.4,
+            noise_scale: 0.05,
+        }
+    }
+}
+
+impl Material for TerrainMaterial {
+    fn vertex_shader() -> ShaderRef {
+        "material/terrain.wgsl".into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        "material/terrain.wgsl".into()
+    }
+
+    fn specialize(
+        pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayout,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        // INFO: the prepass is specialized here as well, but runs the default prepass shader with
+        // its own vertex layout, the normal prepass reads the normal at location 2
+        if pipeline.vertex_shader.as_ref() != Some(&descriptor.vertex.shader) {
+            return Ok(());
+        }
+
+        let vertex_layout = layout.get_layout(&[
+            Mesh::ATTRIBUTE_POSITION.at_shader_location(0),
+            Mesh::ATTRIBUTE_NORMAL.at_shader_location(1),
+            Mesh::ATTRIBUTE_COLOR.at_shader_location(2),
+            ATTRIBUTE_BIOME_WEIGHTS.at_shader_location(3),
+        ])?;
+        descriptor.vertex.buffers = vec![vertex_layout];
+
+        Ok(())
+    }
+}
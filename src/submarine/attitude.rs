@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::RigidBody;
+
+// INFO: angles in radians, zero while the submarine lies level
+#[derive(Clone, Component, Debug, Default)]
+pub struct AttitudePropertyComponent {
+    /// Positive while the bow points up.
+    pub pitch: f32,
+    /// Positive while the starboard side hangs down.
+    pub roll: f32,
+}
+
+type TransformAttitudePropertyTuple<'a> = (&'a Transform, &'a mut AttitudePropertyComponent);
+
+pub fn update_attitude_property(
+    mut query: Query<TransformAttitudePropertyTuple, (Changed<Transform>, With<RigidBody>)>,
+) {
+    for (transform, mut property) in query.iter_mut() {
+        property.pitch = transform.forward().y.clamp(-1.0, 1.0).asin();
+        property.roll = (-transform.right().y).clamp(-1.0, 1.0).asin();
+    }
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::{ExternalImpulse, RapierConfiguration};
+
+use crate::physics;
+
+use super::{module::ballast::WATER_DENSITY, surface::SurfacePropertyComponent};
+
+// INFO: volume in m³ of water displaced by the submerged hull, the center of buoyancy lies
+// above the center of mass, so the hull rights itself
+#[derive(Clone, Component, Debug)]
+pub struct BuoyancyComponent {
+    pub hull_volume: f32,
+    pub center_offset: Vec3,
+}
+
+/// Pushes the hull up by the weight of the water it displaces.
+pub fn apply_buoyancy(
+    rapier_configuration: Res<RapierConfiguration>,
+    time: Res<Time>,
+    mut query: Query<(
+        &BuoyancyComponent,
+        &SurfacePropertyComponent,
+        &Transform,
+        &mut ExternalImpulse,
+    )>,
+) {
+    let dt = physics::get_step_delta_seconds(&rapier_configuration, &time);
+
+    for (buoyancy, surface_property, transform, mut impulse) in query.iter_mut() {
+        let displaced_mass = buoyancy.hull_volume * surface_property.submersion * WATER_DENSITY;
+        let force = -rapier_configuration.gravity * displaced_mass;
+        let lever = transform.rotation * buoyancy.center_offset;
+
+        impulse.impulse += force * dt;
+        impulse.torque_impulse += lever.cross(force) * dt;
+    }
+}
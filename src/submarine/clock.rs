@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+
+use crate::terrain::sky::WorldClock;
+
+use super::settings::{KeyAction, KeyActionEvent, KeyActionMap, KeyPress};
+
+// INFO: a quarter day jumps from dawn to noon to dusk to midnight
+const SKIP_TIME_OF_DAY: f32 = 0.25;
+const SCALE_FACTOR: f32 = 2.0;
+const SCALE_MAX: f32 = 64.0;
+
+/// Controls to speed up, slow down and skip the day/night cycle, only bound in debug builds.
+pub fn get_debug_key_actions() -> Vec<KeyActionMap> {
+    if !cfg!(debug_assertions) {
+        return Vec::new();
+    }
+
+    vec![
+        KeyActionMap {
+            key_code: KeyCode::Period,
+            key_action: KeyAction::ClockFaster,
+        },
+        KeyActionMap {
+            key_code: KeyCode::Comma,
+            key_action: KeyAction::ClockSlower,
+        },
+        KeyActionMap {
+            key_code: KeyCode::N,
+            key_action: KeyAction::ClockSkip,
+        },
+    ]
+}
+
+pub fn on_key_action_event(
+    mut key_action_event_reader: EventReader<KeyActionEvent>,
+    mut clock: ResMut<WorldClock>,
+) {
+    for key_action_event in key_action_event_reader.iter() {
+        if key_action_event.key_press != KeyPress::Down {
+            continue;
+        }
+
+        match key_action_event.key_map.key_action {
+            KeyAction::ClockPause => {
+                if clock.is_paused() {
+                    clock.resume();
+                } else {
+                    clock.pause();
+                }
+            }
+            KeyAction::ClockFaster => {
+                let scale = (clock.get_scale() * SCALE_FACTOR).min(SCALE_MAX);
+                clock.set_scale(scale);
+            }
+            KeyAction::ClockSlower => {
+                let scale = (clock.get_scale() / SCALE_FACTOR).max(1.0 / SCALE_MAX);
+                clock.set_scale(scale);
+            }
+            KeyAction::ClockSkip => {
+                let time_of_day = clock.get_time_of_day() + SKIP_TIME_OF_DAY;
+                clock.set_time_of_day(time_of_day);
+            }
+            _ => (),
+        }
+    }
+}
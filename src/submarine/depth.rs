@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::{RigidBody, Velocity};
+
+use super::surface::SurfacePropertyComponent;
+
+#[derive(Clone, Component, Debug, Default)]
+pub struct DepthPropertyComponent {
+    /// Distance below the local wave surface, zero above it.
+    pub current_depth: f32,
+    /// Rate of climb in m/s, negative while diving.
+    pub vertical_speed: f32,
+}
+
+type TransformDepthPropertyTuple<'a> = (
+    &'a Transform,
+    &'a Velocity,
+    &'a SurfacePropertyComponent,
+    &'a mut DepthPropertyComponent,
+);
+
+pub fn update_depth_property(mut query: Query<TransformDepthPropertyTuple, With<RigidBody>>) {
+    for (transform, velocity, surface_property, mut property) in query.iter_mut() {
+        property.current_depth = (surface_property.wave_height - transform.translation.y).max(0.0);
+        property.vertical_speed = velocity.linvel.y;
+    }
+}
@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::{ExternalImpulse, RapierConfiguration, ReadMassProperties, Velocity};
+
+use crate::{ocean::current::OceanCurrents, physics};
+
+use super::{module::ballast::WATER_DENSITY, surface::SurfacePropertyComponent};
+
+// INFO: kg per m³ of air at sea level
+const AIR_DENSITY: f32 = 1.225;
+
+// INFO: axes of the hull, x lateral, y vertical and z forward, areas in m², the angular
+// coefficients include the hull area and lever arm
+#[derive(Clone, Component, Debug)]
+pub struct DragComponent {
+    pub coefficients: Vec3,
+    pub areas: Vec3,
+    pub angular_coefficients: Vec3,
+}
+
+/// Slows the hull relative to the moving water or the air by their dynamic pressure.
+// https://en.wikipedia.org/wiki/Drag_equation
+pub fn apply_drag(
+    rapier_configuration: Res<RapierConfiguration>,
+    time: Res<Time>,
+    currents: Res<OceanCurrents>,
+    mut query: Query<(
+        &DragComponent,
+        &SurfacePropertyComponent,
+        &Transform,
+        &Velocity,
+        &ReadMassProperties,
+        &mut ExternalImpulse,
+    )>,
+) {
+    let dt = physics::get_step_delta_seconds(&rapier_configuration, &time);
+    let elapsed = time.elapsed_seconds_wrapped();
+
+    for (drag, surface_property, transform, velocity, mass_properties, mut impulse) in
+        query.iter_mut()
+    {
+        let density = AIR_DENSITY + (WATER_DENSITY - AIR_DENSITY) * surface_property.submersion;
+        let inverse_rotation = transform.rotation.inverse();
+
+        let current = currents.get_velocity(transform.translation, elapsed);
+        let linear = inverse_rotation * (velocity.linvel - current * surface_property.submersion);
+        let force = -0.5 * density * drag.coefficients * drag.areas * linear * linear.abs();
+        // INFO: the drag is integrated explicitly, so it may at most stop the hull in one step
+        let linear_impulse = (force * dt).clamp(
+            -linear.abs() * mass_properties.0.mass,
+            linear.abs() * mass_properties.0.mass,
+        );
+
+        let angular = inverse_rotation * velocity.angvel;
+        let torque = -0.5 * density * drag.angular_coefficients * angular * angular.abs();
+        let angular_impulse = (torque * dt).clamp(
+            -angular.abs() * mass_properties.0.principal_inertia,
+            angular.abs() * mass_properties.0.principal_inertia,
+        );
+
+        impulse.impulse += transform.rotation * linear_impulse;
+        impulse.torque_impulse += transform.rotation * angular_impulse;
+    }
+}
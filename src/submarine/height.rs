@@ -1,9 +1,15 @@
 use bevy::prelude::*;
-use bevy_rapier3d::prelude::{QueryFilter, RapierContext, RigidBody};
+use bevy_rapier3d::prelude::{RapierContext, RigidBody};
+
+use crate::collision::CollisionLayer;
+
+// INFO: range in m of the altimeter, below it the seabed is out of range
+pub const HEIGHT_RANGE: f32 = 251.0;
 
 #[derive(Clone, Component, Debug, Default)]
 pub struct HeightPropertyComponent {
-    pub current_height: f32,
+    /// Altitude above the seabed, `None` while the seabed is out of range.
+    pub current_height: Option<f32>,
 }
 
 type TransformHeightPropertyTuple<'a> = (&'a Transform, &'a mut HeightPropertyComponent);
@@ -13,16 +19,12 @@ pub fn update_height_property(
     mut query: Query<TransformHeightPropertyTuple, (Changed<Transform>, With<RigidBody>)>,
 ) {
     let direction = Vec3::new(0.0, -1.0, 0.0);
-    let toi_max = 251.0;
-    // TODO: rework to collision group for ground
-    let filter = QueryFilter::only_fixed();
+    let filter = CollisionLayer::Terrain.get_query_filter();
 
     for (transform, mut property) in query.iter_mut() {
         let origin = transform.translation;
-        if let Some((_entity, toi)) =
-            rapier_context.cast_ray(origin, direction, toi_max, true, filter)
-        {
-            property.current_height = toi.abs();
-        }
+        property.current_height = rapier_context
+            .cast_ray(origin, direction, HEIGHT_RANGE, true, filter)
+            .map(|(_entity, toi)| toi.abs());
     }
 }
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+use crate::{color::RED, submarine::hull::HullDamagedEvent};
+
+// INFO: alpha of the flash per integrity lost, the flash fades out by FLASH_DECAY per second
+const FLASH_PER_DAMAGE: f32 = 0.05;
+const FLASH_MAX: f32 = 0.5;
+const FLASH_DECAY: f32 = 1.0;
+
+#[derive(Component, Default)]
+pub struct DamageFlashUiComponent {
+    intensity: f32,
+}
+
+pub fn setup(commands: &mut Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                size: Size::all(Val::Percent(100.0)),
+                ..default()
+            },
+            background_color: Color::NONE.into(),
+            ..default()
+        },
+        DamageFlashUiComponent::default(),
+    ));
+}
+
+/// Tints the screen red when the hull of the player takes collision damage.
+pub fn update_damage_flash(
+    time: Res<Time>,
+    mut hull_damaged_events: EventReader<HullDamagedEvent>,
+    query: Query<Entity, With<Camera>>,
+    mut ui_query: Query<(&mut BackgroundColor, &mut DamageFlashUiComponent)>,
+) {
+    if let Ok((mut background_color, mut flash)) = ui_query.get_single_mut() {
+        let damage: f32 = hull_damaged_events
+            .iter()
+            .filter(|event| query.contains(event.entity))
+            .map(|event| event.damage)
+            .sum();
+
+        let decay = FLASH_DECAY * time.delta_seconds();
+        let intensity = (flash.intensity + damage * FLASH_PER_DAMAGE - decay).clamp(0.0, FLASH_MAX);
+
+        if intensity != flash.intensity {
+            flash.intensity = intensity;
+            background_color.0 = RED.with_a(intensity);
+        }
+    }
+}
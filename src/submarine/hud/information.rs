@@ -1,17 +1,45 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::Velocity;
 
-use crate::submarine::{
-    height::HeightPropertyComponent, module::engine::EngineComponent,
-    power::PowerCapacitorComponent,
+use crate::{
+    color::RED,
+    submarine::{
+        attitude::AttitudePropertyComponent,
+        depth::DepthPropertyComponent,
+        height::{HeightPropertyComponent, HEIGHT_RANGE},
+        hull::HullComponent,
+        module::engine::EngineComponent,
+        power::PowerCapacitorComponent,
+    },
+    terrain::{
+        deposit::{DepositComponent, DepositDiscoveredEvent},
+        sky::{DaylightEvent, WorldClock},
+    },
 };
 
+#[derive(Default, Component)]
+pub struct AttitudeUiComponent {}
+
 #[derive(Default, Component)]
 pub struct CapacityUiComponent {}
 
+#[derive(Default, Component)]
+pub struct ClockUiComponent {}
+
+#[derive(Default, Component)]
+pub struct DepositUiComponent {
+    count: usize,
+}
+
+#[derive(Default, Component)]
+pub struct DepthUiComponent {}
+
 #[derive(Default, Component)]
 pub struct HeightUiComponent {}
 
+#[derive(Default, Component)]
+pub struct IntegrityUiComponent {}
+
 #[derive(Default, Component)]
 pub struct ThrustUiComponent {}
 
@@ -39,7 +67,7 @@ pub fn setup(commands: &mut Commands, asset_server: &Res<AssetServer>) {
                     style: Style {
                         flex_direction: FlexDirection::Column,
                         gap: Size::height(Val::Px(5.0)),
-                        size: Size::all(Val::Px(100.0)),
+                        size: Size::new(Val::Px(100.0), Val::Px(150.0)),
                         align_content: AlignContent::FlexEnd,
                         justify_content: JustifyContent::Center,
                         ..default()
@@ -49,7 +77,10 @@ pub fn setup(commands: &mut Commands, asset_server: &Res<AssetServer>) {
                 .with_children(|builder| {
                     add_velocity_node(builder, font.clone());
                     add_thrust_node(builder, font.clone());
+                    add_depth_node(builder, font.clone());
                     add_height_node(builder, font.clone());
+                    add_attitude_node(builder, font.clone());
+                    add_integrity_node(builder, font.clone());
                 });
 
             builder.spawn(NodeBundle {
@@ -74,11 +105,125 @@ pub fn setup(commands: &mut Commands, asset_server: &Res<AssetServer>) {
                     ..default()
                 })
                 .with_children(|builder| {
-                    add_capacity_node(builder, font);
+                    add_capacity_node(builder, font.clone());
+                    add_deposit_node(builder, font.clone());
+                    add_clock_node(builder, font);
                 });
         });
 }
 
+fn add_attitude_node(builder: &mut ChildBuilder, font: Handle<Font>) {
+    builder.spawn((
+        TextBundle::from_sections([
+            TextSection::new(
+                "",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 15.0,
+                    color: Color::WHITE,
+                },
+            ),
+            TextSection::new(
+                "° pitch ",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 15.0,
+                    color: Color::WHITE,
+                },
+            ),
+            TextSection::new(
+                "",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 15.0,
+                    color: Color::WHITE,
+                },
+            ),
+            TextSection::new(
+                "° roll",
+                TextStyle {
+                    font,
+                    font_size: 15.0,
+                    color: Color::WHITE,
+                },
+            ),
+        ])
+        .with_style(Style {
+            align_self: AlignSelf::FlexEnd,
+            ..default()
+        }),
+        AttitudeUiComponent::default(),
+    ));
+}
+
+pub fn update_attitude_node(
+    mut ui_query: Query<&mut Text, With<AttitudeUiComponent>>,
+    query: Query<&AttitudePropertyComponent, With<Camera>>,
+) {
+    if let Ok(property) = query.get_single() {
+        if let Ok(mut text) = ui_query.get_single_mut() {
+            text.sections[0].value = format!("{:.0}", property.pitch.to_degrees());
+            text.sections[2].value = format!("{:.0}", property.roll.to_degrees());
+        }
+    }
+}
+
+fn add_depth_node(builder: &mut ChildBuilder, font: Handle<Font>) {
+    builder.spawn((
+        TextBundle::from_sections([
+            TextSection::new(
+                "",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 15.0,
+                    color: Color::WHITE,
+                },
+            ),
+            TextSection::new(
+                " m deep ",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 15.0,
+                    color: Color::WHITE,
+                },
+            ),
+            TextSection::new(
+                "",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 15.0,
+                    color: Color::WHITE,
+                },
+            ),
+            TextSection::new(
+                " m/s",
+                TextStyle {
+                    font,
+                    font_size: 15.0,
+                    color: Color::WHITE,
+                },
+            ),
+        ])
+        .with_style(Style {
+            align_self: AlignSelf::FlexEnd,
+            ..default()
+        }),
+        DepthUiComponent::default(),
+    ));
+}
+
+pub fn update_depth_node(
+    mut ui_query: Query<&mut Text, With<DepthUiComponent>>,
+    query: Query<&DepthPropertyComponent, With<Camera>>,
+) {
+    if let Ok(property) = query.get_single() {
+        if let Ok(mut text) = ui_query.get_single_mut() {
+            text.sections[0].value = format!("{:.1}", property.current_depth);
+            text.sections[2].value = format!("{:+.2}", property.vertical_speed);
+        }
+    }
+}
+
 fn add_height_node(builder: &mut ChildBuilder, font: Handle<Font>) {
     builder.spawn((
         TextBundle::from_sections([
@@ -91,7 +236,7 @@ fn add_height_node(builder: &mut ChildBuilder, font: Handle<Font>) {
                 },
             ),
             TextSection::new(
-                " m",
+                " m above seabed",
                 TextStyle {
                     font,
                     font_size: 15.0,
@@ -113,7 +258,52 @@ pub fn update_height_node(
 ) {
     if let Ok(property) = query.get_single() {
         if let Ok(mut text) = ui_query.get_single_mut() {
-            text.sections[0].value = format!("{:.2}", property.current_height);
+            text.sections[0].value = match property.current_height {
+                Some(height) => format!("{:.2}", height),
+                None => format!("> {:.0}", HEIGHT_RANGE),
+            };
+        }
+    }
+}
+
+fn add_integrity_node(builder: &mut ChildBuilder, font: Handle<Font>) {
+    builder.spawn((
+        TextBundle::from_sections([
+            TextSection::new(
+                "",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 15.0,
+                    color: Color::WHITE,
+                },
+            ),
+            TextSection::new(
+                " % hull",
+                TextStyle {
+                    font,
+                    font_size: 15.0,
+                    color: Color::WHITE,
+                },
+            ),
+        ])
+        .with_style(Style {
+            align_self: AlignSelf::FlexEnd,
+            ..default()
+        }),
+        IntegrityUiComponent::default(),
+    ));
+}
+
+pub fn update_integrity_node_on_hull_component_changed(
+    query: Query<&HullComponent, (With<Camera>, Changed<HullComponent>)>,
+    mut ui_query: Query<&mut Text, With<IntegrityUiComponent>>,
+) {
+    if let Ok(hull) = query.get_single() {
+        if let Ok(mut text) = ui_query.get_single_mut() {
+            let integrity = hull.integrity / hull.integrity_max;
+
+            text.sections[0].value = format!("{:.0}", integrity * 100.0);
+            text.sections[0].style.color = if integrity < 0.25 { RED } else { Color::WHITE };
         }
     }
 }
@@ -174,6 +364,126 @@ pub fn update_capacity_node_on_capacitor_componend_changed(
     }
 }
 
+fn add_clock_node(builder: &mut ChildBuilder, font: Handle<Font>) {
+    builder.spawn((
+        TextBundle::from_sections([
+            TextSection::new(
+                "",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 15.0,
+                    color: Color::WHITE,
+                },
+            ),
+            TextSection::new(
+                "",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 15.0,
+                    color: Color::WHITE,
+                },
+            ),
+            TextSection::new(
+                "",
+                TextStyle {
+                    font,
+                    font_size: 15.0,
+                    color: Color::WHITE,
+                },
+            ),
+        ])
+        .with_style(Style {
+            align_self: AlignSelf::FlexEnd,
+            ..default()
+        }),
+        ClockUiComponent::default(),
+    ));
+}
+
+pub fn update_clock_node(
+    mut events: EventReader<DaylightEvent>,
+    clock: Res<WorldClock>,
+    mut ui_query: Query<&mut Text, With<ClockUiComponent>>,
+) {
+    if let Ok(mut text) = ui_query.get_single_mut() {
+        let minutes = (clock.get_time_of_day() * 24.0 * 60.0) as u32;
+        text.sections[0].value = format!("{:02}:{:02}", minutes / 60, minutes % 60);
+
+        for event in events.iter() {
+            text.sections[1].value = match event {
+                DaylightEvent::Dawn => " day".to_string(),
+                DaylightEvent::Dusk => " night".to_string(),
+            };
+        }
+
+        text.sections[2].value = if clock.is_paused() {
+            " (paused)".to_string()
+        } else if clock.get_scale() != 1.0 {
+            format!(" (x{})", clock.get_scale())
+        } else {
+            "".to_string()
+        };
+    }
+}
+
+fn add_deposit_node(builder: &mut ChildBuilder, font: Handle<Font>) {
+    builder.spawn((
+        TextBundle::from_sections([
+            TextSection::new(
+                "0",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 15.0,
+                    color: Color::WHITE,
+                },
+            ),
+            TextSection::new(
+                " deposits",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 15.0,
+                    color: Color::WHITE,
+                },
+            ),
+            TextSection::new(
+                "",
+                TextStyle {
+                    font,
+                    font_size: 15.0,
+                    color: Color::WHITE,
+                },
+            ),
+        ])
+        .with_style(Style {
+            align_self: AlignSelf::FlexEnd,
+            ..default()
+        }),
+        DepositUiComponent::default(),
+    ));
+}
+
+pub fn update_deposit_node_on_deposit_discovered(
+    mut events: EventReader<DepositDiscoveredEvent>,
+    deposit_query: Query<&DepositComponent>,
+    mut ui_query: Query<(&mut Text, &mut DepositUiComponent)>,
+) {
+    if let Ok((mut text, mut ui)) = ui_query.get_single_mut() {
+        for event in events.iter() {
+            ui.count += 1;
+            text.sections[0].value = format!("{}", ui.count);
+
+            if let Ok(deposit) = deposit_query.get(event.entity) {
+                text.sections[2].value = format!(
+                    " ({:?} {:.0}%, {:.1} m deep)",
+                    event.kind,
+                    deposit.richness * 100.0,
+                    deposit.depth
+                );
+            }
+        }
+    }
+}
+
 fn add_thrust_node(builder: &mut ChildBuilder, font: Handle<Font>) {
     builder.spawn((
         TextBundle::from_sections([
@@ -0,0 +1,77 @@
+use bevy::{
+    prelude::{shape::RegularPolygon, *},
+    render::view::RenderLayers,
+};
+
+use crate::{
+    color,
+    render::line::{LineMaterial, LineStrip},
+    submarine::settings::{KeyAction, KeyActionEvent, KeyMapComponent, KeyPress},
+    terrain::contour::{ContourMapCameraComponent, MAP_LAYER},
+};
+
+// INFO: keeps the marker above every contour line
+const MARKER_HEIGHT: f32 = 100.0;
+
+#[derive(Component)]
+pub struct MapMarkerComponent {}
+
+pub fn setup(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    line_materials: &mut ResMut<Assets<LineMaterial>>,
+) {
+    commands.spawn((
+        MaterialMeshBundle {
+            mesh: meshes.add(
+                LineStrip::from(RegularPolygon {
+                    radius: 6.0,
+                    sides: 3,
+                })
+                .into(),
+            ),
+            material: line_materials.add(LineMaterial {
+                color: color::TURQUOISE,
+            }),
+            ..default()
+        },
+        RenderLayers::layer(MAP_LAYER),
+        MapMarkerComponent {},
+    ));
+}
+
+pub fn on_key_action_event(
+    mut key_action_event_reader: EventReader<KeyActionEvent>,
+    mut query: Query<&mut Camera, With<ContourMapCameraComponent>>,
+) {
+    for key_action_event in key_action_event_reader.iter() {
+        if key_action_event.key_press != KeyPress::Down {
+            continue;
+        }
+
+        if let KeyAction::ToggleMap = key_action_event.key_map.key_action {
+            for mut camera in query.iter_mut() {
+                camera.is_active = !camera.is_active;
+            }
+        }
+    }
+}
+
+/// Moves the marker to the position of the submarine, pointing in its heading.
+pub fn update_map_marker(
+    query: Query<&GlobalTransform, (With<Camera>, With<KeyMapComponent>)>,
+    mut marker_query: Query<&mut Transform, With<MapMarkerComponent>>,
+) {
+    if let (Ok(global_transform), Ok(mut transform)) =
+        (query.get_single(), marker_query.get_single_mut())
+    {
+        let position = global_transform.translation();
+        let forward = global_transform.forward();
+        let heading = (-forward.x).atan2(-forward.z);
+
+        // INFO: the polygon lies in the xy plane pointing up, so it is laid flat pointing forward
+        transform.translation = Vec3::new(position.x, MARKER_HEIGHT, position.z);
+        transform.rotation =
+            Quat::from_rotation_y(heading) * Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2);
+    }
+}
@@ -1,24 +1,32 @@
 use bevy::prelude::*;
 
+use crate::render::line::LineMaterial;
+
 use super::module::{requirement::RequirementComponent, *};
 
 pub mod condition;
 mod crosshair;
+pub mod damage;
 pub mod information;
+pub mod map;
 pub mod module;
 
 pub fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut line_materials: ResMut<Assets<LineMaterial>>,
     query: Query<&Children, With<Camera>>,
     module_query: Query<(&ModuleDetailsComponent, Option<&Children>)>,
     requirements_query: Query<&RequirementComponent>,
 ) {
     info!("setup_hud");
 
+    damage::setup(&mut commands);
     condition::setup(&mut commands);
     crosshair::setup(&mut commands, &asset_server);
     information::setup(&mut commands, &asset_server);
+    map::setup(&mut commands, &mut meshes, &mut line_materials);
     module::setup(
         &mut commands,
         &asset_server,
@@ -0,0 +1,215 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::{ContactForceEvent, RapierContext};
+use rand::Rng;
+
+use super::{
+    depth::DepthPropertyComponent,
+    module::{
+        ballast::{BallastPump, BallastTankComponent},
+        ModuleStateComponent, ModuleStatus,
+    },
+};
+
+// INFO: contact force in N the hull withstands, above the thrust of the engines
+pub const COLLISION_FORCE_THRESHOLD: f32 = 300.0 * 1000.0;
+// INFO: integrity lost per N s of contact impulse above the threshold
+const COLLISION_DAMAGE: f32 = 0.0005;
+
+// INFO: depth in m the basic hull is rated for, well above the deepest open water
+pub const BASIC_RATED_DEPTH: f32 = 50.0;
+// INFO: fraction of the rated depth below which the pressure warning shows
+pub const PRESSURE_WARNING_FRACTION: f32 = 0.9;
+
+// INFO: integrity lost per second at twice the rated depth
+const CRUSH_DAMAGE: f32 = 10.0;
+// INFO: chance per second of every running module to fail at twice the rated depth
+const MODULE_FAILURE_CHANCE: f64 = 0.2;
+
+// INFO: depth in m, below the rated depth the pressure deforms the hull
+#[derive(Clone, Component, Debug)]
+pub struct HullComponent {
+    pub rated_depth: f32,
+    pub integrity: f32,
+    pub integrity_max: f32,
+}
+
+impl HullComponent {
+    pub fn is_breached(&self) -> bool {
+        self.integrity <= 0.0
+    }
+
+    /// Returns how far the water pressure exceeds the rated pressure, as a fraction of it.
+    pub fn get_overpressure(&self, depth: f32) -> f32 {
+        ((depth - self.rated_depth) / self.rated_depth).max(0.0)
+    }
+
+    /// Integrity lost per second at the depth.
+    pub fn get_crush_damage(&self, depth: f32) -> f32 {
+        let overpressure = self.get_overpressure(depth);
+
+        // INFO: the damage grows faster than the pressure, so diving deeper is never worth it
+        CRUSH_DAMAGE * overpressure * (1.0 + overpressure)
+    }
+
+    /// Chance per second of every running module to fail at the depth.
+    pub fn get_module_failure_chance(&self, depth: f32) -> f64 {
+        MODULE_FAILURE_CHANCE * self.get_overpressure(depth) as f64
+    }
+}
+
+#[derive(Clone)]
+pub struct HullDamagedEvent {
+    pub entity: Entity,
+    pub damage: f32,
+}
+
+/// Damages the hull by the part of the contact impulse above the threshold, the collider may be
+/// the hull itself or one of its children. The events carry the force of the last physics step,
+/// so the impulse is taken over the timestep of that step.
+pub fn apply_collision_damage(
+    rapier_context: Res<RapierContext>,
+    mut contact_force_events: EventReader<ContactForceEvent>,
+    parent_query: Query<&Parent>,
+    mut hull_query: Query<&mut HullComponent>,
+    mut hull_damaged_events: EventWriter<HullDamagedEvent>,
+) {
+    let dt = rapier_context.integration_parameters.dt;
+
+    for event in contact_force_events.iter() {
+        let excess_force = event.total_force_magnitude - COLLISION_FORCE_THRESHOLD;
+        if excess_force <= 0.0 {
+            continue;
+        }
+
+        for collider in [event.collider1, event.collider2] {
+            let entity = match parent_query.get(collider) {
+                Ok(parent) if !hull_query.contains(collider) => parent.get(),
+                _ => collider,
+            };
+
+            if let Ok(mut hull) = hull_query.get_mut(entity) {
+                let damage = excess_force * dt * COLLISION_DAMAGE;
+                hull.integrity = (hull.integrity - damage).max(0.0);
+
+                hull_damaged_events.send(HullDamagedEvent { entity, damage });
+            }
+        }
+    }
+}
+
+pub fn apply_pressure_damage(
+    time: Res<Time>,
+    mut query: Query<(&DepthPropertyComponent, &mut HullComponent)>,
+) {
+    for (depth_property, mut hull) in query.iter_mut() {
+        let damage = hull.get_crush_damage(depth_property.current_depth) * time.delta_seconds();
+        if damage <= 0.0 {
+            continue;
+        }
+
+        hull.integrity = (hull.integrity - damage).max(0.0);
+    }
+}
+
+pub fn fail_modules_by_pressure(
+    time: Res<Time>,
+    query: Query<(&DepthPropertyComponent, &HullComponent, &Children)>,
+    mut module_query: Query<&mut ModuleStateComponent>,
+) {
+    let mut rng = rand::thread_rng();
+
+    for (depth_property, hull, children) in query.iter() {
+        let failure_chance = hull.get_module_failure_chance(depth_property.current_depth);
+        if failure_chance <= 0.0 {
+            continue;
+        }
+
+        let chance = (failure_chance * time.delta_seconds_f64()).min(1.0);
+
+        let mut module_iter = module_query.iter_many_mut(children);
+        while let Some(mut state_component) = module_iter.fetch_next() {
+            let is_running = matches!(
+                state_component.state.status(),
+                ModuleStatus::Active | ModuleStatus::ActiveInvalidTrigger | ModuleStatus::Triggered
+            );
+
+            if is_running && rng.gen_bool(chance) {
+                warn!("Module failed under pressure.");
+                state_component.state.next(ModuleStatus::ShuttingDown);
+            }
+        }
+    }
+}
+
+/// Floods the ballast tanks and keeps every module shut down while the hull is breached.
+pub fn handle_hull_breach(
+    query: Query<(&HullComponent, &Children)>,
+    mut module_query: Query<&mut ModuleStateComponent>,
+    mut ballast_query: Query<&mut BallastTankComponent>,
+) {
+    for (hull, children) in query.iter() {
+        if !hull.is_breached() {
+            continue;
+        }
+
+        let mut module_iter = module_query.iter_many_mut(children);
+        while let Some(mut state_component) = module_iter.fetch_next() {
+            if matches!(
+                state_component.state.status(),
+                ModuleStatus::Active | ModuleStatus::ActiveInvalidTrigger | ModuleStatus::Triggered
+            ) {
+                state_component.state.next(ModuleStatus::ShuttingDown);
+            }
+        }
+
+        let mut ballast_iter = ballast_query.iter_many_mut(children);
+        while let Some(mut tank) = ballast_iter.fetch_next() {
+            if tank.pump != BallastPump::Flooding {
+                tank.pump = BallastPump::Flooding;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ocean::OceanWaves, terrain::SEABED_MIN};
+
+    use super::*;
+
+    // INFO: the center of the hull stays half its collider height above the seabed
+    const HULL_HALF_HEIGHT: f32 = 2.0;
+
+    fn get_basic_hull() -> HullComponent {
+        HullComponent {
+            rated_depth: BASIC_RATED_DEPTH,
+            integrity: 100.0,
+            integrity_max: 100.0,
+        }
+    }
+
+    #[test]
+    fn no_pressure_effects_above_rated_depth() {
+        let hull = get_basic_hull();
+
+        assert_eq!(hull.get_crush_damage(hull.rated_depth), 0.0);
+        assert_eq!(hull.get_module_failure_chance(hull.rated_depth), 0.0);
+        assert_eq!(hull.get_crush_damage(0.0), 0.0);
+    }
+
+    #[test]
+    fn every_pressure_stage_is_reached_in_open_water() {
+        let hull = get_basic_hull();
+        let depth_max = OceanWaves::default().sea_level - SEABED_MIN - HULL_HALF_HEIGHT;
+
+        // INFO: the warning, the compression condition and the damage all set in before the
+        // seabed, with some water left below each of them
+        let warning_depth = hull.rated_depth * PRESSURE_WARNING_FRACTION;
+        assert!(warning_depth < depth_max - 10.0);
+        assert!(hull.rated_depth < depth_max - 10.0);
+
+        // INFO: near the seabed the hull loses half its integrity within a minute
+        assert!(hull.get_crush_damage(depth_max) * 60.0 > hull.integrity_max / 2.0);
+        assert!(hull.get_module_failure_chance(depth_max) > 0.05);
+    }
+}
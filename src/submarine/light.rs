@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+
+use crate::terrain::sky::DaylightEvent;
+
+use super::settings::{KeyAction, KeyActionEvent, KeyPress};
+
+// INFO: bright enough to light the seabed in the abyss, where neither sun nor fog do
+const HEADLIGHT_INTENSITY: f32 = 4000.0 * 1000.0;
+const HEADLIGHT_RANGE: f32 = 80.0;
+
+#[derive(Clone, Component, Default)]
+pub struct HeadlightComponent {}
+
+impl HeadlightComponent {
+    pub fn new(builder: &mut ChildBuilder) {
+        builder.spawn((
+            SpotLightBundle {
+                spot_light: SpotLight {
+                    color: Color::rgb(1.0, 0.95, 0.85),
+                    intensity: HEADLIGHT_INTENSITY,
+                    range: HEADLIGHT_RANGE,
+                    inner_angle: 0.3,
+                    outer_angle: 0.6,
+                    ..default()
+                },
+                // INFO: below the bow, so the light throws shadows the camera can see
+                transform: Transform::from_xyz(0.0, -1.5, -5.5),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            HeadlightComponent::default(),
+        ));
+    }
+}
+
+pub fn on_key_action_event(
+    mut key_action_event_reader: EventReader<KeyActionEvent>,
+    mut query: Query<&mut Visibility, With<HeadlightComponent>>,
+) {
+    for key_action_event in key_action_event_reader.iter() {
+        if key_action_event.key_press != KeyPress::Down {
+            continue;
+        }
+
+        if let KeyAction::ToggleLight = key_action_event.key_map.key_action {
+            for mut visibility in query.iter_mut() {
+                *visibility = match *visibility {
+                    Visibility::Hidden => Visibility::Inherited,
+                    _ => Visibility::Hidden,
+                };
+            }
+        }
+    }
+}
+
+/// Switches the headlight on at dusk and off at dawn, it can still be toggled in between.
+pub fn on_daylight_event(
+    mut daylight_event_reader: EventReader<DaylightEvent>,
+    mut query: Query<&mut Visibility, With<HeadlightComponent>>,
+) {
+    for daylight_event in daylight_event_reader.iter() {
+        for mut visibility in query.iter_mut() {
+            *visibility = match daylight_event {
+                DaylightEvent::Dawn => Visibility::Hidden,
+                DaylightEvent::Dusk => Visibility::Inherited,
+            };
+        }
+    }
+}
@@ -9,24 +9,48 @@ use bevy::{
 use bevy_atmosphere::prelude::AtmosphereCamera;
 use bevy_rapier3d::prelude::*;
 
-use crate::{render::force_field::ForceFieldMaterial, submarine::height::HeightPropertyComponent};
+use crate::{
+    collision::CollisionLayer,
+    render::force_field::ForceFieldMaterial,
+    submarine::{
+        attitude::AttitudePropertyComponent,
+        buoyancy::BuoyancyComponent,
+        depth::DepthPropertyComponent,
+        drag::DragComponent,
+        height::HeightPropertyComponent,
+        hull::{HullComponent, HullDamagedEvent},
+        light::HeadlightComponent,
+        surface::SurfacePropertyComponent,
+    },
+};
 
 use self::{
     module::{
         action::{self, ressource_scanner},
-        aftercast,
-        condition::{self, update_engine_stop_condition_by_module_state},
+        aftercast, ballast,
+        condition::{
+            self, hull_breach::HullBreachConditionComponent, pressure::PressureConditionComponent,
+            update_engine_stop_condition_by_module_state,
+        },
         engine, requirement, startup,
     },
     power::*,
     settings::*,
 };
 
+mod attitude;
+mod buoyancy;
+mod clock;
+mod depth;
+pub mod drag;
 mod height;
 mod hud;
+mod hull;
+mod light;
 mod module;
 mod power;
 mod settings;
+mod surface;
 
 #[derive(Default)]
 pub struct SubmarinePlugin {}
@@ -35,13 +59,17 @@ impl Plugin for SubmarinePlugin {
     fn build(&self, app: &mut App) {
         app //
             .add_event::<KeyActionEvent>()
+            .add_event::<HullDamagedEvent>()
             .add_system(setup_player_submarine.on_startup())
             .add_system(hud::setup.on_startup().in_base_set(StartupSet::PostStartup))
             //
             .add_systems(
                 (
                     // update properties
+                    attitude::update_attitude_property,
+                    depth::update_depth_property.after(surface::update_surface_property),
                     height::update_height_property,
+                    surface::update_surface_property,
                     // handle passive effects
                     // TODO: PassiveComponent
                 )
@@ -58,6 +86,8 @@ impl Plugin for SubmarinePlugin {
                     startup::update_module_startup_state_transition,
                     // handle automatic condition state transitions
                     update_engine_stop_condition_by_module_state,
+                    condition::pressure::update_pressure_condition_by_depth,
+                    condition::hull_breach::update_hull_breach_condition_by_integrity,
                 )
                     .in_base_set(CoreSet::PreUpdate),
             )
@@ -76,6 +106,9 @@ impl Plugin for SubmarinePlugin {
                     engine::on_key_action_event,
                     engine::on_mouse_position_change,
                     module::on_key_action_event,
+                    ballast::on_key_action_event,
+                    light::on_key_action_event,
+                    clock::on_key_action_event,
                     // handle conditions
                     condition::engine_stop::update_engine_by_engine_stop_condition,
                     // calculate power usage
@@ -91,11 +124,23 @@ impl Plugin for SubmarinePlugin {
                 )
                     .chain(),
             )
+            .add_system(light::on_daylight_event)
+            // physics
+            .add_systems((
+                ballast::update_ballast_tanks,
+                buoyancy::apply_buoyancy,
+                drag::apply_drag,
+                hull::apply_collision_damage,
+                hull::apply_pressure_damage,
+                hull::fail_modules_by_pressure,
+                hull::handle_hull_breach,
+            ))
             .add_systems(
                 (
                     // actions
                     ressource_scanner::activate,
                     ressource_scanner::deactivate_on_aftercast,
+                    ressource_scanner::discover_deposits,
                 )
                     .in_base_set(CoreSet::PostUpdate),
             )
@@ -104,9 +149,24 @@ impl Plugin for SubmarinePlugin {
                     // ui
                     hud::condition::update_condition_row_ui_component,
                     hud::information::update_capacity_node_on_capacitor_componend_changed,
+                    hud::information::update_clock_node,
+                    hud::information::update_deposit_node_on_deposit_discovered,
+                    hud::damage::update_damage_flash,
+                    hud::information::update_attitude_node,
+                    hud::information::update_depth_node,
                     hud::information::update_height_node,
+                    hud::information::update_integrity_node_on_hull_component_changed,
                     hud::information::update_thrust_node_on_engine_component_changed,
                     hud::information::update_velocity_node,
+                    hud::map::on_key_action_event,
+                    hud::map::update_map_marker,
+                )
+                    .chain()
+                    .in_base_set(CoreSet::PostUpdate),
+            )
+            .add_systems(
+                (
+                    // module ui
                     hud::module::reset_consumption_ui_component,
                     hud::module::reset_cooldown_ui_component,
                     hud::module::update_modules_by_module_state,
@@ -196,7 +256,30 @@ fn setup_player_submarine(
                             key_code: KeyCode::Key3,
                             key_action: KeyAction::ModuleActivation03,
                         },
-                    ],
+                        KeyActionMap {
+                            key_code: KeyCode::M,
+                            key_action: KeyAction::ToggleMap,
+                        },
+                        KeyActionMap {
+                            key_code: KeyCode::R,
+                            key_action: KeyAction::BallastFlood,
+                        },
+                        KeyActionMap {
+                            key_code: KeyCode::F,
+                            key_action: KeyAction::BallastBlow,
+                        },
+                        KeyActionMap {
+                            key_code: KeyCode::L,
+                            key_action: KeyAction::ToggleLight,
+                        },
+                        KeyActionMap {
+                            key_code: KeyCode::P,
+                            key_action: KeyAction::ClockPause,
+                        },
+                    ]
+                    .into_iter()
+                    .chain(clock::get_debug_key_actions())
+                    .collect(),
                 },
             ),
             // power management
@@ -213,26 +296,61 @@ fn setup_player_submarine(
             (
                 RigidBody::Dynamic,
                 ExternalForce::default(),
+                ExternalImpulse::default(),
                 Velocity::default(),
-                Damping {
-                    linear_damping: 1.0,
-                    angular_damping: 1.0,
-                },
-                GravityScale(0.0),
+                GravityScale(1.0),
             ),
             // properties
             (
                 AdditionalMassProperties::Mass(0.0),
+                BuoyancyComponent {
+                    hull_volume: 12.5,
+                    center_offset: Vec3::new(0.0, 0.5, 0.0),
+                },
+                DragComponent {
+                    coefficients: Vec3::new(1.0, 1.1, 0.3),
+                    areas: Vec3::new(40.0, 40.0, 16.0),
+                    angular_coefficients: Vec3::new(870.0, 870.0, 390.0),
+                },
+                ReadMassProperties::default(),
+                AttitudePropertyComponent::default(),
+                DepthPropertyComponent::default(),
                 HeightPropertyComponent::default(),
+                HullComponent {
+                    rated_depth: hull::BASIC_RATED_DEPTH,
+                    integrity: 100.0,
+                    integrity_max: 100.0,
+                },
+                // INFO: half the height of the hull collider
+                SurfacePropertyComponent::new(2.0),
             ),
         ))
         .with_children(|builder| {
             builder.spawn((
                 Collider::cuboid(2.0, 2.0, 5.0),
+                CollisionLayer::Vessels.get_collision_groups(),
                 ColliderMassProperties::Mass(6.0 * 1000.0), // kg
+                ActiveEvents::CONTACT_FORCE_EVENTS,
+                ContactForceEventThreshold(hull::COLLISION_FORCE_THRESHOLD),
             ));
 
             ressource_scanner::new_basic(&asset_server, builder, &mut meshes, &mut materials);
             engine::new_basic(&asset_server, builder);
+            ballast::new_basic(builder);
+            HeadlightComponent::new(builder);
+
+            PressureConditionComponent::new(
+                &asset_server,
+                builder,
+                "submarine/condition/pressure-gauge_33px.png",
+                hull::PRESSURE_WARNING_FRACTION,
+            );
+            PressureConditionComponent::new(
+                &asset_server,
+                builder,
+                "submarine/condition/compression_33px.png",
+                1.0,
+            );
+            HullBreachConditionComponent::new(&asset_server, builder);
         });
 }
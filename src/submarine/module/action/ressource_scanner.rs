@@ -9,6 +9,7 @@ use crate::{
         aftercast::ModuleAftercastComponent, condition::engine_stop::EngineStopConditionComponent,
         requirement::MaximumHeightRequirementComponent, startup::ModuleStartupComponent, *,
     },
+    terrain::deposit::{DepositComponent, DepositDiscoveredEvent, DiscoveredDepositComponent},
 };
 
 use super::ChannelingComponent;
@@ -92,6 +93,44 @@ pub fn activate(
     }
 }
 
+/// Reveals every undiscovered deposit inside the expanding sphere of a scanner.
+pub fn discover_deposits(
+    mut commands: Commands,
+    mut events: EventWriter<DepositDiscoveredEvent>,
+    scanner_query: Query<&GlobalTransform, With<ExpandingSphereEffectComponent>>,
+    mut deposit_query: Query<
+        (Entity, &GlobalTransform, &DepositComponent, &mut Visibility),
+        Without<DiscoveredDepositComponent>,
+    >,
+) {
+    for scanner_transform in scanner_query.iter() {
+        let (scale, _, translation) = scanner_transform.to_scale_rotation_translation();
+        if scale.x <= 0.0 {
+            continue;
+        }
+
+        for (entity, transform, deposit, mut visibility) in deposit_query.iter_mut() {
+            if transform.translation().distance(translation) > scale.x {
+                continue;
+            }
+
+            info!(
+                "Discovered {:?} deposit holding {:.0} units.",
+                deposit.kind, deposit.amount
+            );
+
+            *visibility = Visibility::Visible;
+            commands
+                .entity(entity)
+                .insert(DiscoveredDepositComponent::default());
+            events.send(DepositDiscoveredEvent {
+                entity,
+                kind: deposit.kind,
+            });
+        }
+    }
+}
+
 pub fn deactivate_on_aftercast(
     mut query: Query<(
         &ModuleStateComponent,
@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+
+use crate::submarine::settings::{KeyAction, KeyActionEvent, KeyPress};
+
+use super::ModuleMassComponent;
+
+// INFO: kg per m³ of sea water
+pub const WATER_DENSITY: f32 = 1025.0;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum BallastPump {
+    #[default]
+    Idle,
+    Flooding,
+    Blowing,
+}
+
+// INFO: volumes in m³, flow rate in m³/s
+#[derive(Clone, Component, Debug, Default)]
+pub struct BallastTankComponent {
+    pub capacity: f32,
+    pub water: f32,
+    pub flow_rate: f32,
+    pub dry_mass: f32,
+    pub pump: BallastPump,
+}
+
+impl BallastTankComponent {
+    pub fn get_mass(&self) -> f32 {
+        self.dry_mass + self.water * WATER_DENSITY
+    }
+}
+
+pub fn new_basic(builder: &mut ChildBuilder) {
+    let tank = BallastTankComponent {
+        capacity: 4.0,
+        water: 2.25,
+        flow_rate: 0.25,
+        dry_mass: 0.5 * 1000.0,
+        ..default()
+    };
+
+    builder.spawn((
+        ModuleMassComponent {
+            mass: tank.get_mass(),
+            ..default()
+        },
+        tank,
+    ));
+}
+
+/// Runs the pumps while the ballast keys are held.
+pub fn on_key_action_event(
+    mut key_action_event_reader: EventReader<KeyActionEvent>,
+    query: Query<&Children, With<Camera>>,
+    mut child_query: Query<&mut BallastTankComponent>,
+) {
+    if let Ok(children) = query.get_single() {
+        for key_action_event in key_action_event_reader.iter() {
+            let pump = match (
+                &key_action_event.key_map.key_action,
+                &key_action_event.key_press,
+            ) {
+                (KeyAction::BallastFlood | KeyAction::BallastBlow, KeyPress::Release) => {
+                    BallastPump::Idle
+                }
+                (KeyAction::BallastFlood, _) => BallastPump::Flooding,
+                (KeyAction::BallastBlow, _) => BallastPump::Blowing,
+                _ => continue,
+            };
+
+            let mut child_iter = child_query.iter_many_mut(children);
+            while let Some(mut tank) = child_iter.fetch_next() {
+                if tank.pump != pump {
+                    tank.pump = pump.clone();
+                }
+            }
+        }
+    }
+}
+
+/// Moves water in or out of the tanks and passes the new mass on to the module mass.
+pub fn update_ballast_tanks(
+    time: Res<Time>,
+    mut query: Query<(&mut BallastTankComponent, &mut ModuleMassComponent)>,
+) {
+    for (mut tank, mut mass_component) in query.iter_mut() {
+        let flow = tank.flow_rate * time.delta_seconds();
+        let water = match tank.pump {
+            BallastPump::Idle => continue,
+            BallastPump::Flooding => (tank.water + flow).min(tank.capacity),
+            BallastPump::Blowing => (tank.water - flow).max(0.0),
+        };
+
+        if water != tank.water {
+            tank.water = water;
+            mass_component.mass = tank.get_mass();
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+use crate::submarine::hull::HullComponent;
+
+use super::{engine_stop::EngineStopConditionComponent, ConditionComponent, ConditionStatus};
+
+#[derive(Clone, Component, Default)]
+pub struct HullBreachConditionComponent {}
+
+impl HullBreachConditionComponent {
+    pub fn new(asset_server: &Res<AssetServer>, builder: &mut ChildBuilder) {
+        builder.spawn((
+            ConditionComponent::new(asset_server.load("submarine/condition/hull-breach_33px.png")),
+            EngineStopConditionComponent::default(),
+            HullBreachConditionComponent::default(),
+        ));
+    }
+}
+
+pub fn update_hull_breach_condition_by_integrity(
+    mut query: Query<(&Parent, &mut ConditionComponent), With<HullBreachConditionComponent>>,
+    hull_query: Query<&HullComponent, Changed<HullComponent>>,
+) {
+    for (parent, mut state) in query.iter_mut() {
+        if let Ok(hull) = hull_query.get(parent.get()) {
+            let status = if hull.is_breached() {
+                ConditionStatus::Active
+            } else {
+                ConditionStatus::Inactive
+            };
+
+            if state.status != status {
+                state.status = status;
+            }
+        }
+    }
+}
@@ -4,6 +4,8 @@ use uuid::Uuid;
 use super::{ModuleStateComponent, ModuleStatus};
 
 pub mod engine_stop;
+pub mod hull_breach;
+pub mod pressure;
 
 #[derive(Clone, Component, Debug)]
 pub struct ConditionComponent {
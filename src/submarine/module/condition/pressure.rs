@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+use crate::submarine::{depth::DepthPropertyComponent, hull::HullComponent};
+
+use super::{ConditionComponent, ConditionStatus};
+
+// INFO: active below this fraction of the rated depth of the hull
+#[derive(Clone, Component)]
+pub struct PressureConditionComponent {
+    pub depth_fraction: f32,
+}
+
+impl PressureConditionComponent {
+    pub fn new(
+        asset_server: &Res<AssetServer>,
+        builder: &mut ChildBuilder,
+        icon: &'static str,
+        depth_fraction: f32,
+    ) {
+        builder.spawn((
+            ConditionComponent::new(asset_server.load(icon)),
+            PressureConditionComponent { depth_fraction },
+        ));
+    }
+}
+
+pub fn update_pressure_condition_by_depth(
+    mut query: Query<(
+        &Parent,
+        &mut ConditionComponent,
+        &PressureConditionComponent,
+    )>,
+    hull_query: Query<(&DepthPropertyComponent, &HullComponent)>,
+) {
+    for (parent, mut state, condition) in query.iter_mut() {
+        if let Ok((depth_property, hull)) = hull_query.get(parent.get()) {
+            let status =
+                if depth_property.current_depth > hull.rated_depth * condition.depth_fraction {
+                    ConditionStatus::Active
+                } else {
+                    ConditionStatus::Inactive
+                };
+
+            if state.status != status {
+                state.status = status;
+            }
+        }
+    }
+}
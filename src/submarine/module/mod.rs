@@ -10,6 +10,7 @@ use super::{
 
 pub mod action;
 pub mod aftercast;
+pub mod ballast;
 pub mod condition;
 pub mod engine;
 pub mod requirement;
@@ -29,7 +30,8 @@ pub struct ModuleDetailsComponent {
 
 #[derive(Component, Default)]
 pub struct ModuleMassComponent {
-    pub initialized: bool,
+    /// Part of the mass already added to the ship.
+    pub applied_mass: f32,
     pub mass: f32,
 }
 
@@ -159,25 +161,26 @@ pub fn on_key_action_event(
 }
 
 pub fn update_mass_by_module_mass(
-    mut query: Query<(&mut AdditionalMassProperties, &Children), Changed<AdditionalMassProperties>>,
-    mut child_query: Query<&mut ModuleMassComponent>,
+    mut query: Query<(&mut AdditionalMassProperties, &Children)>,
+    mut child_query: Query<&mut ModuleMassComponent, Changed<ModuleMassComponent>>,
 ) {
     for (mut additional_mass, children) in query.iter_mut() {
         let mut child_iter = child_query.iter_many_mut(children);
         while let Some(mut mass_component) = child_iter.fetch_next() {
-            if mass_component.initialized {
+            let difference = mass_component.mass - mass_component.applied_mass;
+            if difference == 0.0 {
                 continue;
             }
 
             if let AdditionalMassProperties::Mass(mass) = *additional_mass {
-                let mass = mass + mass_component.mass;
+                let mass = mass + difference;
 
-                info!("Mass set to {} kg.", mass);
+                debug!("Mass set to {} kg.", mass);
 
                 *additional_mass = AdditionalMassProperties::Mass(mass);
             }
 
-            mass_component.initialized = true;
+            mass_component.applied_mass = mass_component.mass;
         }
     }
 }
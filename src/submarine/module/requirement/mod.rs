@@ -84,13 +84,14 @@ pub fn handle_maximum_height_requirement(
             match height_property_query
                 .get_component::<HeightPropertyComponent>(module_parent.get())
             {
-                Ok(height_property) => {
-                    if height_property.current_height <= requirement.maximum_height {
+                Ok(height_property) => match height_property.current_height {
+                    Some(height) if height <= requirement.maximum_height => {
                         state.status = RequirementStatus::Fulfilled;
-                    } else {
+                    }
+                    _ => {
                         state.status = RequirementStatus::Violated;
                     }
-                }
+                },
                 Err(_) => {
                     warn!("module parent does not have HeightPropertyComponent!")
                 }
@@ -45,6 +45,14 @@ pub enum KeyAction {
     ModuleActivation01,
     ModuleActivation02,
     ModuleActivation03,
+    ToggleMap,
+    BallastFlood,
+    BallastBlow,
+    ToggleLight,
+    ClockPause,
+    ClockFaster,
+    ClockSlower,
+    ClockSkip,
 }
 
 pub fn handle_key_presses(
@@ -98,5 +106,13 @@ fn get_standard_key_press_for_action_event(event: &KeyAction) -> KeyPress {
         KeyAction::ModuleActivation01 => KeyPress::Down,
         KeyAction::ModuleActivation02 => KeyPress::Down,
         KeyAction::ModuleActivation03 => KeyPress::Down,
+        KeyAction::ToggleMap => KeyPress::Down,
+        KeyAction::BallastFlood => KeyPress::Hold,
+        KeyAction::BallastBlow => KeyPress::Hold,
+        KeyAction::ToggleLight => KeyPress::Down,
+        KeyAction::ClockPause => KeyPress::Down,
+        KeyAction::ClockFaster => KeyPress::Down,
+        KeyAction::ClockSlower => KeyPress::Down,
+        KeyAction::ClockSkip => KeyPress::Down,
     }
 }
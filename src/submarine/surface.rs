@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::RigidBody;
+
+use crate::ocean::OceanWaves;
+
+#[derive(Clone, Component, Debug)]
+pub struct SurfacePropertyComponent {
+    pub half_height: f32,
+    /// World height of the wave surface at the position of the body.
+    pub wave_height: f32,
+    /// 0.0 above and 1.0 below the surface.
+    pub submersion: f32,
+}
+
+impl SurfacePropertyComponent {
+    pub fn new(half_height: f32) -> Self {
+        Self {
+            half_height,
+            wave_height: 0.0,
+            submersion: 1.0,
+        }
+    }
+
+    /// Fraction of the hull below the wave surface, for a hull centered at `y`.
+    fn get_submersion(&self, y: f32) -> f32 {
+        let bottom = y - self.half_height;
+        ((self.wave_height - bottom) / (self.half_height * 2.0)).clamp(0.0, 1.0)
+    }
+}
+
+type TransformSurfacePropertyTuple<'a> = (&'a Transform, &'a mut SurfacePropertyComponent);
+
+pub fn update_surface_property(
+    ocean_waves: Res<OceanWaves>,
+    time: Res<Time>,
+    mut query: Query<TransformSurfacePropertyTuple, With<RigidBody>>,
+) {
+    let elapsed = time.elapsed_seconds_wrapped();
+
+    for (transform, mut property) in query.iter_mut() {
+        let position = transform.translation;
+
+        property.wave_height = ocean_waves.get_height(position.x, position.z, elapsed);
+        property.submersion = property.get_submersion(position.y);
+    }
+}
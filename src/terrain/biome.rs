@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+use deep_voronoi::TectonicMap;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::color;
+
+// INFO: heights are fractions between the lowest and highest point of the height map
+const TRENCH_HEIGHT: f32 = 0.2;
+// INFO: rise per ground unit, 1.0 is a 45° slope
+const ROCKY_SLOPE: f32 = 0.8;
+// INFO: chance of a Voronoi cell outside of trenches to become a vent field
+const VENT_FIELD_CHANCE: f64 = 0.05;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Biome {
+    SandFlats,
+    RockySlopes,
+    VentField,
+    Trench,
+}
+
+impl Biome {
+    /// Colors of the lowest and the highest possible point of the biome.
+    pub fn get_palette(&self) -> [Color; 2] {
+        match self {
+            Biome::SandFlats => [color::SLATE_BLUE, color::TIFFANY_BLUE],
+            Biome::RockySlopes => [color::GRAPE, color::UNITED_NATIONS_BLUE],
+            Biome::VentField => [color::AERO, color::AQUAMARINE],
+            Biome::Trench => [color::FRENCH_VIOLET, color::GRAPE],
+        }
+    }
+
+    /// One-hot weights in the order of `ATTRIBUTE_BIOME_WEIGHTS`.
+    pub fn get_weights(&self) -> [f32; 4] {
+        let mut weights = [0.0; 4];
+        weights[*self as usize] = 1.0;
+
+        weights
+    }
+
+    pub fn get_color(&self, height: f32) -> [f32; 4] {
+        let [low, high] = self.get_palette().map(|color| color.as_rgba_f32());
+        let height = height.clamp(0.0, 1.0);
+
+        [0, 1, 2, 3].map(|channel| low[channel] + (high[channel] - low[channel]) * height)
+    }
+}
+
+/// Biome per height map pixel.
+#[derive(Resource)]
+pub struct BiomeMap {
+    width: u32,
+    height: u32,
+    height_min: f32,
+    height_range: f32,
+    biomes: Vec<Biome>,
+}
+
+impl BiomeMap {
+    pub fn generate(
+        tectonic_map: &TectonicMap,
+        height_multiplier: f32,
+        ground_multiplier: f32,
+        seed: u64,
+    ) -> Self {
+        let height_map = &tectonic_map.height_map;
+        let (width, height) = height_map.dimensions();
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let vent_cells: Vec<bool> = (0..tectonic_map.diagram.cells.len())
+            .map(|_| rng.gen_bool(VENT_FIELD_CHANCE))
+            .collect();
+
+        let get_height = |x: i64, y: i64| {
+            let x = x.clamp(0, width as i64 - 1) as u32;
+            let y = y.clamp(0, height as i64 - 1) as u32;
+
+            height_map.get_pixel(x, y).0[0] as f32 / u16::MAX as f32
+        };
+
+        let (height_min, height_max) = height_map
+            .pixels()
+            .fold((u16::MAX, u16::MIN), |(min, max), pixel| {
+                (min.min(pixel.0[0]), max.max(pixel.0[0]))
+            });
+        let height_min = height_min as f32 / u16::MAX as f32;
+        let height_range = (height_max as f32 / u16::MAX as f32 - height_min).max(f32::EPSILON);
+
+        let mut biomes = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let (x, y) = (x as i64, y as i64);
+                let pixel_height = get_relative_height(get_height(x, y), height_min, height_range);
+
+                let slope = Vec2::new(
+                    get_height(x + 1, y) - get_height(x - 1, y),
+                    get_height(x, y + 1) - get_height(x, y - 1),
+                ) * height_multiplier
+                    / (2.0 * ground_multiplier);
+
+                let cell = tectonic_map.cell_ids.get_pixel(x as u32, y as u32).0[0] as usize;
+
+                let biome = if pixel_height < TRENCH_HEIGHT {
+                    Biome::Trench
+                } else if slope.length() > ROCKY_SLOPE {
+                    Biome::RockySlopes
+                } else if vent_cells[cell] {
+                    Biome::VentField
+                } else {
+                    Biome::SandFlats
+                };
+
+                biomes.push(biome);
+            }
+        }
+
+        Self {
+            width,
+            height,
+            height_min,
+            height_range,
+            biomes,
+        }
+    }
+
+    /// Converts a height map value into a fraction between the lowest and highest point.
+    pub fn get_relative_height(&self, height: f32) -> f32 {
+        get_relative_height(height, self.height_min, self.height_range)
+    }
+
+    /// Returns the biome at a height map position, positions outside of the map are clamped.
+    pub fn get_biome(&self, x: u32, y: u32) -> Biome {
+        let x = x.min(self.width - 1);
+        let y = y.min(self.height - 1);
+
+        self.biomes[(y * self.width + x) as usize]
+    }
+}
+
+fn get_relative_height(height: f32, height_min: f32, height_range: f32) -> f32 {
+    ((height - height_min) / height_range).clamp(0.0, 1.0)
+}
@@ -0,0 +1,414 @@
+use bevy::{
+    prelude::*,
+    render::{mesh::Indices, render_resource::PrimitiveTopology},
+};
+use bevy_rapier3d::prelude::{Collider, RigidBody};
+use deep_voronoi::{Bounds, Vector2f64, Vector3f32};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    collision::CollisionLayer,
+    render::terrain::{TerrainMaterial, ATTRIBUTE_BIOME_WEIGHTS},
+};
+
+use super::{
+    biome::{Biome, BiomeMap},
+    TerrainHeightMap, WorldSeed,
+};
+
+// INFO: size of the worley cells forming the tunnels, arches and chambers
+const TUNNEL_SCALE: f32 = 48.0;
+const ARCH_SCALE: f32 = 32.0;
+const CHAMBER_SCALE: f32 = 48.0;
+// INFO: thresholds in cell units, tunnels and chambers leave room for the submarine
+const TUNNEL_WIDTH: f32 = 0.2;
+const ARCH_WIDTH: f32 = 0.1;
+const CHAMBER_RADIUS: f32 = 0.35;
+// INFO: how far caves reach below and arches reach above the seabed
+const CAVE_DEPTH: f32 = 32.0;
+const ARCH_HEIGHT: f32 = 12.0;
+// INFO: distance from the border of a region over which caves and arches fade out, so the
+// voxel surface meets the heightfield
+const BORDER_FADE: f32 = 12.0;
+
+#[derive(Resource)]
+pub struct CaveSettings {
+    pub region_count: usize,
+    /// Side length of a square cave region.
+    pub region_size: f32,
+    pub voxel_size: f32,
+}
+
+impl Default for CaveSettings {
+    fn default() -> Self {
+        Self {
+            region_count: 3,
+            region_size: 96.0,
+            voxel_size: 2.0,
+        }
+    }
+}
+
+/// World space square of the seabed replaced by voxel terrain.
+#[derive(Clone, Copy, Debug)]
+pub struct CaveRegion {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl CaveRegion {
+    fn get_border_distance(&self, point: Vec2) -> f32 {
+        (point - self.min).min(self.max - point).min_element()
+    }
+}
+
+#[derive(Component)]
+pub struct CaveComponent {}
+
+/// Samples of a density field on a regular grid, positive values are solid.
+struct DensityField {
+    origin: Vec3,
+    voxel_size: f32,
+    size: UVec3,
+    values: Vec<f32>,
+}
+
+impl DensityField {
+    fn new<F>(origin: Vec3, voxel_size: f32, size: UVec3, get_density: F) -> Self
+    where
+        F: Fn(Vec3) -> f32,
+    {
+        let mut values = Vec::with_capacity((size.x * size.y * size.z) as usize);
+        for z in 0..size.z {
+            for y in 0..size.y {
+                for x in 0..size.x {
+                    values.push(get_density(
+                        origin + UVec3::new(x, y, z).as_vec3() * voxel_size,
+                    ));
+                }
+            }
+        }
+
+        Self {
+            origin,
+            voxel_size,
+            size,
+            values,
+        }
+    }
+
+    fn get(&self, position: UVec3) -> f32 {
+        let position = position.min(self.size - 1);
+        self.values[((position.z * self.size.y + position.y) * self.size.x + position.x) as usize]
+    }
+}
+
+/// Picks the cave regions on rocky slopes first and spawns their voxel terrain with colliders.
+/// The caller has to mask the returned regions out of the heightfield.
+pub fn spawn_caves(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    material: &Handle<TerrainMaterial>,
+    terrain_height_map: &TerrainHeightMap,
+    biome_map: &BiomeMap,
+    settings: &CaveSettings,
+    world_seed: &WorldSeed,
+) -> Vec<CaveRegion> {
+    let mut rng = StdRng::seed_from_u64(world_seed.derive([0, 0, 0x0ca7_e000]));
+    let regions = get_regions(terrain_height_map, biome_map, settings, rng.gen());
+
+    let offsets: [Vec3; 4] = [(); 4].map(|_| {
+        Vec3::new(
+            rng.gen_range(0.0..1000.0),
+            rng.gen_range(0.0..1000.0),
+            rng.gen_range(0.0..1000.0),
+        )
+    });
+
+    for region in regions.iter() {
+        let get_ground = |point: Vec2| terrain_height_map.get_height(point.x, point.y);
+
+        let steps = ((region.max - region.min) / settings.voxel_size)
+            .ceil()
+            .as_uvec2()
+            + 1;
+        let (ground_min, ground_max) = (0..steps.y)
+            .flat_map(|y| (0..steps.x).map(move |x| UVec2::new(x, y)))
+            .map(|step| get_ground(region.min + step.as_vec2() * settings.voxel_size))
+            .fold((f32::MAX, f32::MIN), |(min, max), ground| {
+                (min.min(ground), max.max(ground))
+            });
+
+        let origin = Vec3::new(
+            region.min.x,
+            ground_min - CAVE_DEPTH - settings.voxel_size * 2.0,
+            region.min.y,
+        );
+        let height = ground_max + ARCH_HEIGHT + settings.voxel_size * 2.0 - origin.y;
+        let size = UVec3::new(
+            steps.x,
+            (height / settings.voxel_size).ceil() as u32 + 1,
+            steps.y,
+        );
+
+        let field = DensityField::new(origin, settings.voxel_size, size, |position| {
+            get_density(position, get_ground(position.xz()), region, &offsets)
+        });
+
+        let (vertices, indices, normals) = generate_mesh(&field);
+        if indices.is_empty() {
+            continue;
+        }
+
+        let mut colors = Vec::with_capacity(vertices.len());
+        let mut biome_weights = Vec::with_capacity(vertices.len());
+        for vertex in vertices.iter() {
+            let local = terrain_height_map.to_local(*vertex);
+            let biome = biome_map.get_biome(local.x.max(0.0) as u32, local.z.max(0.0) as u32);
+
+            colors.push(biome.get_color(local.y));
+            biome_weights.push(Biome::RockySlopes.get_weights());
+        }
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_indices(Some(Indices::U32(
+            indices.iter().flatten().copied().collect(),
+        )));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vertices.clone());
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+        mesh.insert_attribute(ATTRIBUTE_BIOME_WEIGHTS, biome_weights);
+
+        commands.spawn((
+            MaterialMeshBundle {
+                mesh: meshes.add(mesh),
+                material: material.clone(),
+                ..default()
+            },
+            RigidBody::Fixed,
+            Collider::trimesh(vertices, indices),
+            CollisionLayer::Terrain.get_collision_groups(),
+            CaveComponent {},
+        ));
+    }
+
+    regions
+}
+
+fn get_regions(
+    terrain_height_map: &TerrainHeightMap,
+    biome_map: &BiomeMap,
+    settings: &CaveSettings,
+    seed: u64,
+) -> Vec<CaveRegion> {
+    let (min, max) = terrain_height_map.get_bounds();
+    let half_size = Vec2::splat(settings.region_size / 2.0);
+    let inner_min = min + half_size;
+    let inner_max = max - half_size;
+    if inner_min.cmpge(inner_max).any() {
+        return Vec::new();
+    }
+
+    let bounds = Bounds::new(
+        Vector2f64::new(inner_min.x as f64, inner_min.y as f64),
+        Vector2f64::new(inner_max.x as f64, inner_max.y as f64),
+    );
+
+    let mut centers: Vec<(bool, Vec2)> =
+        deep_voronoi::sample_poisson_disk(&bounds, settings.region_size as f64 * 2.0, seed)
+            .into_iter()
+            .map(|point| {
+                let center = Vec2::new(point.x as f32, point.y as f32);
+                let local = terrain_height_map.to_local(Vec3::new(center.x, 0.0, center.y));
+                let is_rocky =
+                    biome_map.get_biome(local.x as u32, local.z as u32) == Biome::RockySlopes;
+
+                (is_rocky, center)
+            })
+            .collect();
+
+    // INFO: stable sort keeps the random order of the samples within both groups
+    centers.sort_by_key(|(is_rocky, _)| !is_rocky);
+
+    centers
+        .into_iter()
+        .take(settings.region_count)
+        .map(|(_, center)| CaveRegion {
+            min: center - half_size,
+            max: center + half_size,
+        })
+        .collect()
+}
+
+/// Solid below the seabed, with tunnels and chambers carved out below and arches added above.
+fn get_density(position: Vec3, ground: f32, region: &CaveRegion, offsets: &[Vec3; 4]) -> f32 {
+    let height = position.y - ground;
+    let border = smoothstep(0.0, BORDER_FADE, region.get_border_distance(position.xz()));
+    let cave_weight = border * (1.0 - smoothstep(CAVE_DEPTH - 8.0, CAVE_DEPTH, -height));
+    let arch_weight = border
+        * smoothstep(0.0, 2.0, height)
+        * (1.0 - smoothstep(ARCH_HEIGHT - 4.0, ARCH_HEIGHT, height));
+
+    let get_distances = |scale: f32, offset: Vec3| {
+        let point = position / scale + offset;
+        deep_voronoi::worley_3d(Vector3f32::new(point.x, point.y, point.z), 1.0)
+    };
+    let get_edge = |scale: f32, offset: Vec3| {
+        let distances = get_distances(scale, offset);
+        distances.y - distances.x
+    };
+
+    // INFO: the noise is only sampled where it has an effect, as it dominates the generation
+    let open = if cave_weight > 0.0 {
+        // INFO: the edges of two worley fields are sheets, their intersection forms tubes
+        let tunnel = get_edge(TUNNEL_SCALE, offsets[0]).max(get_edge(TUNNEL_SCALE, offsets[1]));
+        let tunnel = (TUNNEL_WIDTH - tunnel) * TUNNEL_SCALE;
+        let chamber = (CHAMBER_RADIUS - get_distances(CHAMBER_SCALE, offsets[0]).x) * CHAMBER_SCALE;
+        tunnel.max(chamber)
+    } else {
+        0.0
+    };
+
+    let arch = if arch_weight > 0.0 {
+        let arch = get_edge(ARCH_SCALE, offsets[2]).max(get_edge(ARCH_SCALE, offsets[3]));
+        (ARCH_WIDTH - arch) * ARCH_SCALE
+    } else {
+        0.0
+    };
+
+    (-height)
+        .min(-(open * cave_weight - (1.0 - cave_weight) * BORDER_FADE))
+        .max(arch * arch_weight - (1.0 - arch_weight) * BORDER_FADE)
+}
+
+fn smoothstep(edge_min: f32, edge_max: f32, value: f32) -> f32 {
+    let t = ((value - edge_min) / (edge_max - edge_min)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Meshes the zero crossing of the density field with surface nets, a vertex per crossed voxel
+/// at the average of its edge crossings and a quad per crossed grid edge.
+fn generate_mesh(field: &DensityField) -> (Vec<Vec3>, Vec<[u32; 3]>, Vec<[f32; 3]>) {
+    const CORNERS: [UVec3; 8] = [
+        UVec3::new(0, 0, 0),
+        UVec3::new(1, 0, 0),
+        UVec3::new(0, 1, 0),
+        UVec3::new(1, 1, 0),
+        UVec3::new(0, 0, 1),
+        UVec3::new(1, 0, 1),
+        UVec3::new(0, 1, 1),
+        UVec3::new(1, 1, 1),
+    ];
+    const EDGES: [(usize, usize); 12] = [
+        (0, 1),
+        (2, 3),
+        (4, 5),
+        (6, 7),
+        (0, 2),
+        (1, 3),
+        (4, 6),
+        (5, 7),
+        (0, 4),
+        (1, 5),
+        (2, 6),
+        (3, 7),
+    ];
+
+    let voxels = field.size - 1;
+    let get_voxel_index =
+        |voxel: UVec3| ((voxel.z * voxels.y + voxel.y) * voxels.x + voxel.x) as usize;
+
+    let mut vertices = Vec::new();
+    let mut normals = Vec::new();
+    let mut voxel_vertices = vec![u32::MAX; (voxels.x * voxels.y * voxels.z) as usize];
+
+    for z in 0..voxels.z {
+        for y in 0..voxels.y {
+            for x in 0..voxels.x {
+                let voxel = UVec3::new(x, y, z);
+                let densities = CORNERS.map(|corner| field.get(voxel + corner));
+
+                let mut sum = Vec3::ZERO;
+                let mut count = 0;
+                for (a, b) in EDGES {
+                    if (densities[a] > 0.0) == (densities[b] > 0.0) {
+                        continue;
+                    }
+
+                    let t = densities[a] / (densities[a] - densities[b]);
+                    sum += CORNERS[a].as_vec3().lerp(CORNERS[b].as_vec3(), t);
+                    count += 1;
+                }
+
+                if count == 0 {
+                    continue;
+                }
+
+                let local = sum / count as f32;
+                voxel_vertices[get_voxel_index(voxel)] = vertices.len() as u32;
+                vertices.push(field.origin + (voxel.as_vec3() + local) * field.voxel_size);
+
+                // INFO: the density increases into the solid, so the normal points against
+                // the gradient across the voxel
+                let [d0, d1, d2, d3, d4, d5, d6, d7] = densities;
+                let gradient = Vec3::new(
+                    (d1 - d0) + (d3 - d2) + (d5 - d4) + (d7 - d6),
+                    (d2 - d0) + (d3 - d1) + (d6 - d4) + (d7 - d5),
+                    (d4 - d0) + (d5 - d1) + (d6 - d2) + (d7 - d3),
+                );
+                normals.push((-gradient).normalize_or_zero().to_array());
+            }
+        }
+    }
+
+    let mut indices = Vec::new();
+    let axes = [UVec3::X, UVec3::Y, UVec3::Z];
+
+    for z in 0..field.size.z {
+        for y in 0..field.size.y {
+            for x in 0..field.size.x {
+                let start = UVec3::new(x, y, z);
+
+                for (axis_index, axis) in axes.iter().enumerate() {
+                    let u = axes[(axis_index + 1) % 3];
+                    let v = axes[(axis_index + 2) % 3];
+
+                    // INFO: the four voxels around the edge have to exist
+                    let end = start + *axis;
+                    if end.cmpge(field.size).any()
+                        || start.dot(u) == 0
+                        || start.dot(v) == 0
+                        || start.dot(u) >= voxels.dot(u)
+                        || start.dot(v) >= voxels.dot(v)
+                    {
+                        continue;
+                    }
+
+                    let is_solid = field.get(start) > 0.0;
+                    if is_solid == (field.get(end) > 0.0) {
+                        continue;
+                    }
+
+                    let quad = [start - u - v, start - v, start, start - u]
+                        .map(|voxel| voxel_vertices[get_voxel_index(voxel)]);
+                    if quad.contains(&u32::MAX) {
+                        continue;
+                    }
+
+                    // INFO: the quad winds counter clockwise around the axis, it is flipped
+                    // when the faces have to point against the axis
+                    let [a, b, c, d] = quad;
+                    if is_solid {
+                        indices.push([a, b, c]);
+                        indices.push([a, c, d]);
+                    } else {
+                        indices.push([a, c, b]);
+                        indices.push([a, d, c]);
+                    }
+                }
+            }
+        }
+    }
+
+    (vertices, indices, normals)
+}
@@ -0,0 +1,207 @@
+use bevy::{
+    core_pipeline::{clear_color::ClearColorConfig, tonemapping::Tonemapping},
+    prelude::*,
+    render::{
+        camera::{ScalingMode, Viewport},
+        view::RenderLayers,
+    },
+    window::PrimaryWindow,
+};
+
+use crate::{
+    color,
+    ocean::OceanWaves,
+    render::line::{LineMaterial, LineStrip},
+};
+
+use super::{surface::TerrainSurface, TerrainHeightMap, GROUND_MULTIPLIER, HEIGHT_MULTIPLIER};
+
+// INFO: render layer only seen by the map camera
+pub const MAP_LAYER: u8 = 1;
+// INFO: side length of the map view as a fraction of the smaller window side
+const MAP_VIEWPORT_SIZE: f32 = 0.4;
+const MAP_VIEWPORT_MARGIN: f32 = 16.0;
+
+#[derive(Resource)]
+pub struct ContourSettings {
+    /// Depth between two contour lines.
+    pub interval: f32,
+    /// Every nth contour line is drawn as a major line.
+    pub major_every: u32,
+    /// Distance the lines float above the seabed.
+    pub offset: f32,
+    /// Draws the lines on the seabed as well, otherwise they are only shown on the map.
+    pub in_world: bool,
+}
+
+impl Default for ContourSettings {
+    fn default() -> Self {
+        Self {
+            interval: 4.0,
+            major_every: 5,
+            offset: 0.2,
+            in_world: true,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct ContourComponent {}
+
+#[derive(Component)]
+pub struct ContourMapCameraComponent {}
+
+/// Spawns an iso-depth line strip for every `interval` between the lowest and the highest
+/// point of the height map.
+pub fn spawn_contours(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut line_materials: ResMut<Assets<LineMaterial>>,
+    terrain_height_map: Res<TerrainHeightMap>,
+    surface: Res<TerrainSurface>,
+    settings: Res<ContourSettings>,
+    ocean_waves: Res<OceanWaves>,
+) {
+    let translation = terrain_height_map.translation;
+    let sea_level = ocean_waves.sea_level;
+
+    let minor_material = line_materials.add(LineMaterial {
+        color: color::SLATE_BLUE,
+    });
+    let major_material = line_materials.add(LineMaterial { color: color::AERO });
+
+    let render_layers = if settings.in_world {
+        RenderLayers::from_layers(&[0, MAP_LAYER])
+    } else {
+        RenderLayers::layer(MAP_LAYER)
+    };
+
+    let depth_min = sea_level - (translation.y + HEIGHT_MULTIPLIER);
+    let depth_max = sea_level - translation.y;
+
+    let first = (depth_min / settings.interval).ceil() as i32;
+    let last = (depth_max / settings.interval).floor() as i32;
+
+    for step in first..=last {
+        let depth = step as f32 * settings.interval;
+        let height = sea_level - depth;
+        let level = (height - translation.y) / HEIGHT_MULTIPLIER;
+        if level <= 0.0 || level >= 1.0 {
+            continue;
+        }
+
+        let material = if step % settings.major_every.max(1) as i32 == 0 {
+            major_material.clone()
+        } else {
+            minor_material.clone()
+        };
+
+        for contour in deep_rtin::get_contours(&terrain_height_map.height_map, level) {
+            let points = contour
+                .points
+                .iter()
+                .map(|point| {
+                    let x = point.x * GROUND_MULTIPLIER + translation.x;
+                    let z = point.y * GROUND_MULTIPLIER + translation.z;
+
+                    // INFO: the simplified mesh can be above the exact contour height
+                    let y = match surface.get_surface(x, z) {
+                        Some((position, _)) => position.y.max(height),
+                        None => height,
+                    };
+
+                    Vec3::new(x, y + settings.offset, z)
+                })
+                .collect();
+
+            commands.spawn((
+                MaterialMeshBundle {
+                    mesh: meshes.add(
+                        LineStrip {
+                            points,
+                            close: contour.closed,
+                        }
+                        .into(),
+                    ),
+                    material: material.clone(),
+                    ..default()
+                },
+                render_layers,
+                ContourComponent {},
+            ));
+        }
+    }
+}
+
+/// Spawns the inactive top down camera of the map view, looking at the whole terrain.
+pub fn spawn_map_camera(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    surface: Res<TerrainSurface>,
+) {
+    let (min, max) = surface.get_bounds();
+    let center = (min + max) / 2.0;
+
+    // INFO: the camera must not clear, as clearing ignores the viewport and wipes the window,
+    // so the map draws its own background below the terrain
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(shape::Plane::from_size(max.x - min.x).into()),
+            material: materials.add(StandardMaterial {
+                base_color: color::GRAPE_25,
+                unlit: true,
+                ..default()
+            }),
+            transform: Transform::from_xyz(center.x, -HEIGHT_MULTIPLIER * 4.0, center.y)
+                .with_scale(Vec3::new(1.0, 1.0, (max.y - min.y) / (max.x - min.x))),
+            ..default()
+        },
+        RenderLayers::layer(MAP_LAYER),
+    ));
+
+    commands.spawn((
+        Camera3dBundle {
+            camera: Camera {
+                hdr: true,
+                order: 1,
+                is_active: false,
+                ..default()
+            },
+            camera_3d: Camera3d {
+                clear_color: ClearColorConfig::None,
+                ..default()
+            },
+            tonemapping: Tonemapping::None,
+            projection: Projection::Orthographic(OrthographicProjection {
+                scaling_mode: ScalingMode::FixedVertical(max.y - min.y),
+                ..default()
+            }),
+            transform: Transform::from_xyz(center.x, 500.0, center.y)
+                .looking_at(Vec3::new(center.x, 0.0, center.y), Vec3::NEG_Z),
+            ..default()
+        },
+        RenderLayers::layer(MAP_LAYER),
+        UiCameraConfig { show_ui: false },
+        ContourMapCameraComponent {},
+    ));
+}
+
+/// Keeps the map view as a square in the top right corner of the window.
+pub fn update_map_camera_viewport(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut query: Query<&mut Camera, With<ContourMapCameraComponent>>,
+) {
+    if let (Ok(window), Ok(mut camera)) = (window_query.get_single(), query.get_single_mut()) {
+        let width = window.physical_width();
+        let height = window.physical_height();
+        let margin = (MAP_VIEWPORT_MARGIN * window.scale_factor() as f32) as u32;
+        let size = ((width.min(height) as f32 * MAP_VIEWPORT_SIZE) as u32).max(1);
+
+        camera.viewport = Some(Viewport {
+            physical_position: UVec2::new(width.saturating_sub(size + margin), margin),
+            physical_size: UVec2::splat(size),
+            ..default()
+        });
+    }
+}
@@ -0,0 +1,145 @@
+use std::f32::consts::TAU;
+
+use bevy::{pbr::NotShadowCaster, prelude::*};
+use deep_voronoi::{Bounds, Vector2f64};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::color;
+
+use super::{
+    biome::{Biome, BiomeMap},
+    surface::TerrainSurface,
+    WorldSeed, GROUND_MULTIPLIER,
+};
+
+// INFO: minimum distance between the starting points of two veins
+const VEIN_SPACING: f64 = 48.0;
+const VEIN_STEP_MIN: f32 = 3.0;
+const VEIN_STEP_MAX: f32 = 8.0;
+// INFO: maximum depth below the seabed, scaled by the richness of the vein
+const BURIAL_DEPTH_MAX: f32 = 4.0;
+// INFO: amount held by a deposit with a richness of 1.0
+const AMOUNT_MAX: f32 = 1000.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResourceKind {
+    Manganese,
+    Cobalt,
+    Sulfide,
+    RareEarth,
+}
+
+impl ResourceKind {
+    fn from_biome(biome: Biome) -> Self {
+        match biome {
+            Biome::SandFlats => ResourceKind::Manganese,
+            Biome::RockySlopes => ResourceKind::Cobalt,
+            Biome::VentField => ResourceKind::Sulfide,
+            Biome::Trench => ResourceKind::RareEarth,
+        }
+    }
+
+    fn get_color(&self) -> Color {
+        match self {
+            ResourceKind::Manganese => color::SKY_BLUE,
+            ResourceKind::Cobalt => color::PICTON_BLUE,
+            ResourceKind::Sulfide => color::TURQUOISE,
+            ResourceKind::RareEarth => color::FRENCH_VIOLET,
+        }
+    }
+}
+
+/// Part of a resource vein, hidden until a scanner discovers it. Mining modules draw from
+/// `amount`.
+#[derive(Component, Debug)]
+pub struct DepositComponent {
+    pub kind: ResourceKind,
+    /// Quality of the vein between 0.0 and 1.0.
+    pub richness: f32,
+    pub amount: f32,
+    /// Depth below the seabed.
+    pub depth: f32,
+}
+
+#[derive(Default, Component)]
+pub struct DiscoveredDepositComponent {}
+
+pub struct DepositDiscoveredEvent {
+    pub entity: Entity,
+    pub kind: ResourceKind,
+}
+
+/// Starts veins at Poisson distributed points, their resource is defined by the biome at the
+/// start. Each vein wanders across the seabed as a chain of deposits.
+pub fn spawn_deposits(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    surface: &TerrainSurface,
+    biome_map: &BiomeMap,
+    world_seed: &WorldSeed,
+) {
+    let mut rng = StdRng::seed_from_u64(world_seed.derive([0, 0, 0x00de_9051]));
+    let mesh = meshes.add(
+        shape::Icosphere {
+            radius: 0.5,
+            subdivisions: 1,
+        }
+        .into(),
+    );
+
+    let (min, max) = surface.get_bounds();
+    let bounds = Bounds::new(
+        Vector2f64::new(min.x as f64, min.y as f64),
+        Vector2f64::new(max.x as f64, max.y as f64),
+    );
+
+    for start in deep_voronoi::sample_poisson_disk(&bounds, VEIN_SPACING, rng.gen()) {
+        let mut position = Vec2::new(start.x as f32, start.y as f32);
+        let surface_point = match surface.get_surface(position.x, position.y) {
+            Some((point, _)) => surface.to_local(point) / GROUND_MULTIPLIER,
+            None => continue,
+        };
+
+        let biome = biome_map.get_biome(
+            surface_point.x.max(0.0) as u32,
+            surface_point.z.max(0.0) as u32,
+        );
+        let kind = ResourceKind::from_biome(biome);
+        let richness: f32 = rng.gen_range(0.2..=1.0);
+
+        let color = kind.get_color();
+        let material = materials.add(StandardMaterial {
+            base_color: color,
+            emissive: color,
+            ..default()
+        });
+
+        let mut direction = rng.gen_range(0.0..TAU);
+        for _ in 0..rng.gen_range(3..=8) {
+            if let Some((point, _)) = surface.get_surface(position.x, position.y) {
+                let depth = rng.gen_range(0.0..=BURIAL_DEPTH_MAX * richness);
+
+                commands.spawn((
+                    PbrBundle {
+                        mesh: mesh.clone(),
+                        material: material.clone(),
+                        transform: Transform::from_translation(point - Vec3::Y * depth),
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                    NotShadowCaster,
+                    DepositComponent {
+                        kind,
+                        richness,
+                        amount: AMOUNT_MAX * richness * rng.gen_range(0.5..=1.0),
+                        depth,
+                    },
+                ));
+            }
+
+            direction += rng.gen_range(-0.6..=0.6);
+            position += Vec2::from_angle(direction) * rng.gen_range(VEIN_STEP_MIN..=VEIN_STEP_MAX);
+        }
+    }
+}
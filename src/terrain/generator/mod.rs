@@ -1,26 +1,32 @@
 use bevy::prelude::Vec3;
+use deep_rtin::{HeightMap, Mask};
 
-pub type MeshVertices = (Vec<Vec3>, Vec<[u32; 3]>, Vec<[f32; 3]>, Vec<[f32; 4]>);
+use super::biome::BiomeMap;
+
+pub type MeshVertices = (
+    Vec<Vec3>,
+    Vec<[u32; 3]>,
+    Vec<[f32; 3]>,
+    Vec<[f32; 4]>,
+    Vec<[f32; 4]>,
+);
 
 pub fn generate_mesh(
-    height_map_path: &str,
+    height_map: &HeightMap,
+    mask: &Mask,
+    biome_map: &BiomeMap,
     height_multiplier: f32,
     ground_multiplier: f32,
 ) -> MeshVertices {
-    let height_map = deep_rtin::retrieve_heigth_map(height_map_path);
-    let errors = deep_rtin::get_errors(&height_map);
+    let errors = deep_rtin::get_masked_errors(height_map, mask);
     let triangles = deep_rtin::get_triangles(0.064, &errors);
-    let (vertices, indices, normals) = deep_rtin::generate_mesh_data(&height_map, &triangles);
+    let triangles = deep_rtin::remove_masked_triangles(triangles, mask);
+    let (vertices, indices, normals) = deep_rtin::generate_mesh_data(height_map, &triangles);
 
     let mut colors = Vec::<[f32; 4]>::new();
+    let mut biome_weights = Vec::<[f32; 4]>::new();
     let mut converted_vertices: Vec<Vec3> = Vec::new();
 
-    let gradient = colorgrad::CustomGradient::new()
-        // TODO: map colors to color.rs
-        .html_colors(&["#7400b8", "#6930c3", "#5e60ce", "#64dfdf"])
-        .build()
-        .unwrap();
-
     for vertex in vertices {
         converted_vertices.push(Vec3::new(
             vertex.x * ground_multiplier,
@@ -28,15 +34,10 @@ pub fn generate_mesh(
             vertex.z * ground_multiplier,
         ));
 
-        let color: Vec<f32> = gradient
-            .at(vertex.y as f64)
-            .to_rgba16()
-            .into_iter()
-            .map(|x| (x as f32) / u16::MAX as f32)
-            .collect();
-
-        colors.push([color[0], color[1], color[2], color[3]]);
+        let biome = biome_map.get_biome(vertex.x as u32, vertex.z as u32);
+        colors.push(biome.get_color(biome_map.get_relative_height(vertex.y)));
+        biome_weights.push(biome.get_weights());
     }
 
-    (converted_vertices, indices, normals, colors)
+    (converted_vertices, indices, normals, colors, biome_weights)
 }
@@ -1,15 +1,37 @@
 use bevy::prelude::*;
 use bevy::render::{mesh::Indices, render_resource::PrimitiveTopology};
 use bevy_rapier3d::prelude::{Collider, RigidBody};
+use deep_rtin::{HeightMap, Mask};
+use deep_voronoi::{Bounds, TectonicSettings, Vector2f64};
+use rand::Rng;
+
+use crate::collision::CollisionLayer;
+use crate::render::terrain::{TerrainMaterial, ATTRIBUTE_BIOME_WEIGHTS};
 
+use self::biome::BiomeMap;
+use self::cave::CaveSettings;
+use self::contour::ContourSettings;
+use self::deposit::DepositDiscoveredEvent;
 use self::generator::generate_mesh;
+use self::scatter::ScatterSettings;
 use self::sky::SkyPlugin;
+use self::surface::TerrainSurface;
 
+pub mod biome;
+mod cave;
+pub mod contour;
+pub mod deposit;
 mod generator;
-mod sky;
+mod scatter;
+pub mod sky;
+mod surface;
 
 const GROUND_MULTIPLIER: f32 = 1.0;
 const HEIGHT_MULTIPLIER: f32 = 64.0;
+// INFO: world height of the lowest possible point of the heightfield
+pub const SEABED_MIN: f32 = HEIGHT_MULTIPLIER * -0.75;
+// INFO: side length of the generated height map, deep-rtin requires a power of 2
+const WORLD_SIZE: u32 = 512;
 
 #[derive(Default)]
 pub struct TerrainPlugin {}
@@ -17,7 +39,106 @@ pub struct TerrainPlugin {}
 impl Plugin for TerrainPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugin(SkyPlugin::default())
-            .add_system(spawn_terrain.on_startup());
+            .init_resource::<WorldSeed>()
+            .init_resource::<ScatterSettings>()
+            .init_resource::<CaveSettings>()
+            .init_resource::<ContourSettings>()
+            .add_event::<DepositDiscoveredEvent>()
+            .add_system(spawn_terrain.on_startup())
+            .add_systems(
+                (contour::spawn_contours, contour::spawn_map_camera)
+                    .on_startup()
+                    .in_base_set(StartupSet::PostStartup),
+            )
+            .add_system(contour::update_map_camera_viewport);
+    }
+}
+
+/// Seed every world generation stage derives its randomness from, insert it before the
+/// TerrainPlugin to reproduce a world.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct WorldSeed(pub u64);
+
+impl Default for WorldSeed {
+    fn default() -> Self {
+        Self(rand::thread_rng().gen())
+    }
+}
+
+impl WorldSeed {
+    /// Mixes the values into the seed, so every tile or stage gets an independent but
+    /// reproducible seed.
+    // https://xorshift.di.unimi.it/splitmix64.c
+    pub fn derive(&self, values: [i64; 3]) -> u64 {
+        values.into_iter().fold(self.0, |seed, value| {
+            let mut z = seed ^ (value as u64).wrapping_add(0x9e37_79b9_7f4a_7c15);
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        })
+    }
+}
+
+/// Height map the terrain mesh was generated from, with the translation of the mesh.
+#[derive(Resource)]
+pub struct TerrainHeightMap {
+    pub height_map: HeightMap,
+    pub translation: Vec3,
+}
+
+impl TerrainHeightMap {
+    /// Returns the world space rectangle covered by the height map as minimum and maximum.
+    pub fn get_bounds(&self) -> (Vec2, Vec2) {
+        let size = Vec2::new(
+            self.height_map.width() as f32,
+            self.height_map.height() as f32,
+        );
+
+        (
+            self.translation.xz(),
+            self.translation.xz() + size * GROUND_MULTIPLIER,
+        )
+    }
+
+    /// Converts a world position into height map pixels and a height between 0.0 and 1.0.
+    pub fn to_local(&self, position: Vec3) -> Vec3 {
+        (position - self.translation)
+            / Vec3::new(GROUND_MULTIPLIER, HEIGHT_MULTIPLIER, GROUND_MULTIPLIER)
+    }
+
+    /// Returns the bilinear interpolated world space height at `x`, `z`.
+    pub fn get_height(&self, x: f32, z: f32) -> f32 {
+        let local = self.to_local(Vec3::new(x, 0.0, z)).xz();
+        let max = UVec2::new(self.height_map.width() - 1, self.height_map.height() - 1);
+        let cell = local.floor().max(Vec2::ZERO).as_uvec2().min(max);
+        let fraction = (local - cell.as_vec2()).clamp(Vec2::ZERO, Vec2::ONE);
+
+        let get_pixel = |offset: UVec2| {
+            let pixel = (cell + offset).min(max);
+            self.height_map.get_pixel(pixel.x, pixel.y).0[0] as f32 / u16::MAX as f32
+        };
+
+        let top =
+            get_pixel(UVec2::ZERO) + (get_pixel(UVec2::X) - get_pixel(UVec2::ZERO)) * fraction.x;
+        let bottom =
+            get_pixel(UVec2::Y) + (get_pixel(UVec2::ONE) - get_pixel(UVec2::Y)) * fraction.x;
+
+        (top + (bottom - top) * fraction.y) * HEIGHT_MULTIPLIER + self.translation.y
+    }
+
+    /// Masks every cell lying completely inside one of the world space rectangles, given as
+    /// minimum and maximum.
+    pub fn get_mask(&self, rectangles: &[(Vec2, Vec2)]) -> Mask {
+        Mask::from_fn(self.height_map.width(), self.height_map.height(), |x, y| {
+            let min = self.translation.xz() + Vec2::new(x as f32, y as f32) * GROUND_MULTIPLIER;
+            let max = min + Vec2::splat(GROUND_MULTIPLIER);
+
+            let is_masked = rectangles.iter().any(|(rectangle_min, rectangle_max)| {
+                min.cmpge(*rectangle_min).all() && max.cmple(*rectangle_max).all()
+            });
+
+            [is_masked as u8].into()
+        })
     }
 }
 
@@ -25,103 +146,106 @@ fn spawn_terrain(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    mut terrain_materials: ResMut<Assets<TerrainMaterial>>,
+    world_seed: Res<WorldSeed>,
+    scatter_settings: Res<ScatterSettings>,
+    cave_settings: Res<CaveSettings>,
 ) {
-    testing_voronoi(&mut commands, &mut meshes, &mut materials);
+    let bounds = Bounds::new(Vector2f64::zeros(), Vector2f64::repeat(WORLD_SIZE as f64));
+    let tectonic_map = deep_voronoi::generate_tectonic_map(
+        &bounds,
+        &TectonicSettings::default(),
+        WORLD_SIZE,
+        WORLD_SIZE,
+        world_seed.0,
+    );
+
+    let biome_map = BiomeMap::generate(
+        &tectonic_map,
+        HEIGHT_MULTIPLIER,
+        GROUND_MULTIPLIER,
+        world_seed.0,
+    );
+
+    let translation = Vec3::new(-256.0, SEABED_MIN, -256.0);
+    let terrain_height_map = TerrainHeightMap {
+        height_map: tectonic_map.height_map,
+        translation,
+    };
+    let terrain_material = terrain_materials.add(TerrainMaterial::default());
+
+    let cave_regions = cave::spawn_caves(
+        &mut commands,
+        &mut meshes,
+        &terrain_material,
+        &terrain_height_map,
+        &biome_map,
+        &cave_settings,
+        &world_seed,
+    );
+
+    // INFO: the heightfield leaves a hole for the voxel terrain and overlaps it at the border
+    let margin = Vec2::splat(cave_settings.voxel_size);
+    let mask = terrain_height_map.get_mask(
+        &cave_regions
+            .iter()
+            .map(|region| (region.min + margin, region.max - margin))
+            .collect::<Vec<_>>(),
+    );
 
-    let (mesh_vertices, mesh_indices, normals, colors) = generate_mesh(
-        "assets/height_maps/youbu_bay.png",
+    let (mesh_vertices, mesh_indices, normals, colors, biome_weights) = generate_mesh(
+        &terrain_height_map.height_map,
+        &mask,
+        &biome_map,
         HEIGHT_MULTIPLIER,
         GROUND_MULTIPLIER,
     );
 
+    let surface = TerrainSurface::new(&mesh_vertices, &mesh_indices, translation);
+
     let mesh = generate_mesh_from_base_vectors(
         mesh_vertices.clone(),
         mesh_indices.clone(),
         normals,
         colors,
+        biome_weights,
     );
     let mesh_handle = meshes.add(mesh);
 
     commands.spawn((
-        PbrBundle {
+        MaterialMeshBundle {
             mesh: mesh_handle,
-            material: materials.add(StandardMaterial { ..default() }),
-            transform: Transform::from_translation(Vec3::new(
-                -256.0,
-                HEIGHT_MULTIPLIER * -0.75,
-                -256.0,
-            )),
+            material: terrain_material,
+            transform: Transform::from_translation(translation),
             ..default()
         },
         RigidBody::Fixed,
         Collider::trimesh(mesh_vertices, mesh_indices),
+        CollisionLayer::Terrain.get_collision_groups(),
     ));
-}
 
-use rand::distributions::Uniform;
-use rand::Rng;
+    scatter::spawn_props(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        &surface,
+        &biome_map,
+        &scatter_settings,
+        &world_seed,
+    );
 
-fn testing_voronoi(
-    commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<StandardMaterial>>,
-) {
-    let mut rng = rand::thread_rng();
-    let range1 = Uniform::new(0., 100.);
-    let range2 = Uniform::new(0., 100.);
-
-    let red_material_handle = materials.add(Color::RED.into());
-    (0..10)
-        .map(|_| (rng.sample(&range1), rng.sample(&range2)))
-        .for_each(|pnt| {
-            commands.spawn(PbrBundle {
-                mesh: meshes.add(shape::UVSphere::default().into()),
-                material: red_material_handle.clone(),
-                transform: Transform::from_xyz(pnt.0, 0.0, pnt.1),
-                ..default()
-            });
-        });
-    //
-    // let red_material_handle = materials.add(Color::RED.into());
-    // for point in &points {
-    //     commands.spawn(PbrBundle {
-    //         mesh: meshes.add(shape::UVSphere::default().into()),
-    //         material: red_material_handle.clone(),
-    //         transform: Transform::from_xyz(point.x as f32, 0.0, point.y as f32),
-    //         ..default()
-    //     });
-    // }
-    //
-    // let diagram = CentroidDiagram::new(&points).unwrap();
-    // let blue_material_handle = materials.add(Color::BLUE.into());
-    // for cell in diagram.cells {
-    //     let _points: Vec<Vec2> = cell
-    //         .points()
-    //         .into_iter()
-    //         .map(|pnt| Vec2::new(pnt.x as f32, pnt.y as f32))
-    //         .inspect(|pnt| {
-    //             commands.spawn(PbrBundle {
-    //                 mesh: meshes.add(shape::UVSphere::default().into()),
-    //                 material: blue_material_handle.clone(),
-    //                 transform: Transform::from_xyz(pnt.x, 0.0, pnt.y),
-    //                 ..default()
-    //             });
-    //         })
-    //         .collect();
-    //
-    //     // let polygon = Polygon {
-    //     //     points: _points.into_iter().collect(),
-    //     //     closed: true,
-    //     // };
-    //     //
-    //     // commands.spawn((
-    //     //     ShapeBundle {
-    //     //         path: GeometryBuilder::build_as(&polygon),
-    //     //         ..default()
-    //     //     },
-    //     //     Fill::color(Color::SEA_GREEN),
-    //     // ));
-    // }
+    deposit::spawn_deposits(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        &surface,
+        &biome_map,
+        &world_seed,
+    );
+
+    commands.insert_resource(biome_map);
+    commands.insert_resource(surface);
+    commands.insert_resource(terrain_height_map);
 }
 
 fn generate_mesh_from_base_vectors(
@@ -129,12 +253,14 @@ fn generate_mesh_from_base_vectors(
     indices: Vec<[u32; 3]>,
     normals: Vec<[f32; 3]>,
     colors: Vec<[f32; 4]>,
+    biome_weights: Vec<[f32; 4]>,
 ) -> Mesh {
     let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
     mesh.set_indices(Some(Indices::U32(indices.into_iter().flatten().collect())));
     mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
     mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
     mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    mesh.insert_attribute(ATTRIBUTE_BIOME_WEIGHTS, biome_weights);
 
     mesh
 }
@@ -0,0 +1,335 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Collider;
+use deep_voronoi::{Bounds, Vector2f32, Vector2f64};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{collision::CollisionLayer, color};
+
+use super::{
+    biome::{Biome, BiomeMap},
+    surface::TerrainSurface,
+    WorldSeed, GROUND_MULTIPLIER, HEIGHT_MULTIPLIER,
+};
+
+// INFO: props are grouped and seeded per square tile of this side length
+const TILE_SIZE: f32 = 64.0;
+// INFO: size of the worley cells clumping props together
+const CLUMP_SIZE: f32 = 24.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PropKind {
+    Rock,
+    Kelp,
+    Coral,
+    WreckDebris,
+}
+
+struct PropSettings {
+    spacing_min: f64,
+    spacing_max: f64,
+    slope_max: f32,
+    // INFO: 0.0 keeps the prop upright, 1.0 aligns it to the terrain normal
+    alignment: f32,
+    scale_min: f32,
+    scale_max: f32,
+}
+
+impl PropKind {
+    const ALL: [PropKind; 4] = [
+        PropKind::Rock,
+        PropKind::Kelp,
+        PropKind::Coral,
+        PropKind::WreckDebris,
+    ];
+
+    fn get_settings(&self) -> PropSettings {
+        match self {
+            PropKind::Rock => PropSettings {
+                spacing_min: 4.0,
+                spacing_max: 24.0,
+                slope_max: 60.0f32.to_radians(),
+                alignment: 1.0,
+                scale_min: 0.5,
+                scale_max: 2.0,
+            },
+            PropKind::Kelp => PropSettings {
+                spacing_min: 1.5,
+                spacing_max: 12.0,
+                slope_max: 25.0f32.to_radians(),
+                alignment: 0.0,
+                scale_min: 0.7,
+                scale_max: 1.5,
+            },
+            PropKind::Coral => PropSettings {
+                spacing_min: 2.0,
+                spacing_max: 16.0,
+                slope_max: 35.0f32.to_radians(),
+                alignment: 0.6,
+                scale_min: 0.5,
+                scale_max: 1.2,
+            },
+            PropKind::WreckDebris => PropSettings {
+                spacing_min: 10.0,
+                spacing_max: 60.0,
+                slope_max: 20.0f32.to_radians(),
+                alignment: 1.0,
+                scale_min: 0.6,
+                scale_max: 1.4,
+            },
+        }
+    }
+
+    /// Density between 0.0 and 1.0 by biome and height as a fraction of the height range.
+    fn get_density(&self, biome: Biome, height: f32) -> f64 {
+        match (self, biome) {
+            (PropKind::Rock, Biome::RockySlopes) => 1.0,
+            (PropKind::Rock, Biome::Trench) => 0.5,
+            (PropKind::Rock, Biome::VentField) => 0.4,
+            (PropKind::Rock, Biome::SandFlats) => 0.2,
+            // INFO: kelp only grows on shallow flats
+            (PropKind::Kelp, Biome::SandFlats) => ((height - 0.45) / 0.15).clamp(0.0, 1.0) as f64,
+            (PropKind::Coral, Biome::VentField) => 1.0,
+            (PropKind::Coral, Biome::SandFlats) => 0.6,
+            (PropKind::WreckDebris, Biome::Trench) => 0.3,
+            (PropKind::WreckDebris, Biome::SandFlats) => 0.15,
+            _ => 0.0,
+        }
+    }
+
+    /// Kelp and debris start at the ground and grow along the y axis, rocks and corals are
+    /// centered and end up half buried.
+    fn get_mesh(&self) -> Mesh {
+        match self {
+            PropKind::Rock => shape::Icosphere {
+                radius: 1.0,
+                subdivisions: 1,
+            }
+            .into(),
+            PropKind::Kelp => shape::Box {
+                min_x: -0.1,
+                max_x: 0.1,
+                min_y: 0.0,
+                max_y: 6.0,
+                min_z: -0.025,
+                max_z: 0.025,
+            }
+            .into(),
+            PropKind::Coral => shape::Capsule {
+                radius: 0.4,
+                depth: 1.0,
+                ..default()
+            }
+            .into(),
+            PropKind::WreckDebris => shape::Box {
+                min_x: -1.5,
+                max_x: 1.5,
+                min_y: 0.0,
+                max_y: 1.0,
+                min_z: -0.75,
+                max_z: 0.75,
+            }
+            .into(),
+        }
+    }
+
+    fn get_color(&self) -> Color {
+        match self {
+            PropKind::Rock => color::GRAPE,
+            PropKind::Kelp => color::TURQUOISE,
+            PropKind::Coral => color::FRENCH_VIOLET,
+            PropKind::WreckDebris => color::GRAY,
+        }
+    }
+
+    fn get_collider(&self) -> Option<Collider> {
+        match self {
+            PropKind::Rock => Some(Collider::ball(1.0)),
+            PropKind::WreckDebris => Some(Collider::cuboid(1.5, 0.5, 0.75)),
+            PropKind::Kelp | PropKind::Coral => None,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct ScatterSettings {
+    pub colliders: bool,
+}
+
+impl Default for ScatterSettings {
+    fn default() -> Self {
+        Self { colliders: true }
+    }
+}
+
+#[derive(Component)]
+pub struct PropTileComponent {}
+
+#[derive(Component)]
+pub struct PropComponent {}
+
+/// Spawns a tile entity per TILE_SIZE square of the terrain holding its props as children. The
+/// props of a kind are sampled across the whole terrain, so their spacing also holds across the
+/// tile borders.
+pub fn spawn_props(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    surface: &TerrainSurface,
+    biome_map: &BiomeMap,
+    settings: &ScatterSettings,
+    world_seed: &WorldSeed,
+) {
+    let handles: Vec<(Handle<Mesh>, Handle<StandardMaterial>)> = PropKind::ALL
+        .iter()
+        .map(|kind| {
+            (
+                meshes.add(kind.get_mesh()),
+                materials.add(kind.get_color().into()),
+            )
+        })
+        .collect();
+
+    let (min, max) = surface.get_bounds();
+    let tile_min = (min / TILE_SIZE).floor().as_ivec2();
+    let tile_max = (max / TILE_SIZE).ceil().as_ivec2();
+    let tile_count = tile_max - tile_min;
+
+    let bounds = Bounds::new(
+        Vector2f64::new(min.x as f64, min.y as f64),
+        Vector2f64::new(max.x as f64, max.y as f64),
+    );
+
+    // INFO: samples per kind, grouped by tile
+    let mut tiles =
+        vec![vec![Vec::new(); PropKind::ALL.len()]; (tile_count.x * tile_count.y) as usize];
+    for kind in PropKind::ALL {
+        let seed = world_seed.derive([0, kind as i64, 0x05ca_77e2]);
+
+        for point in sample(kind, &bounds, surface, biome_map, seed) {
+            let tile = (Vec2::new(point.x as f32, point.y as f32) / TILE_SIZE)
+                .floor()
+                .as_ivec2()
+                .clamp(tile_min, tile_max - 1)
+                - tile_min;
+            tiles[(tile.y * tile_count.x + tile.x) as usize][kind as usize].push(point);
+        }
+    }
+
+    for y in tile_min.y..tile_max.y {
+        for x in tile_min.x..tile_max.x {
+            let tile = IVec2::new(x, y) - tile_min;
+            let samples = &tiles[(tile.y * tile_count.x + tile.x) as usize];
+
+            commands
+                .spawn((SpatialBundle::default(), PropTileComponent {}))
+                .with_children(|parent| {
+                    for (kind, (mesh, material)) in PropKind::ALL.iter().zip(handles.iter()) {
+                        let seed = world_seed.derive([x as i64, y as i64, *kind as i64]);
+                        let points = &samples[*kind as usize];
+
+                        for transform in place(*kind, points, surface, biome_map, seed) {
+                            let mut prop = parent.spawn((
+                                PbrBundle {
+                                    mesh: mesh.clone(),
+                                    material: material.clone(),
+                                    transform,
+                                    ..default()
+                                },
+                                PropComponent {},
+                            ));
+
+                            if settings.colliders {
+                                if let Some(collider) = kind.get_collider() {
+                                    prop.insert((
+                                        collider,
+                                        CollisionLayer::Props.get_collision_groups(),
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                });
+        }
+    }
+}
+
+fn get_density(
+    kind: PropKind,
+    point: Vector2f64,
+    surface: &TerrainSurface,
+    biome_map: &BiomeMap,
+) -> f64 {
+    let position = match surface.get_surface(point.x as f32, point.y as f32) {
+        Some((position, _)) => surface.to_local(position),
+        None => return 0.0,
+    };
+
+    let biome = biome_map.get_biome(
+        (position.x / GROUND_MULTIPLIER).max(0.0) as u32,
+        (position.z / GROUND_MULTIPLIER).max(0.0) as u32,
+    );
+    let height = biome_map.get_relative_height(position.y / HEIGHT_MULTIPLIER);
+
+    let clump = deep_voronoi::worley_2d(
+        Vector2f32::new(point.x as f32, point.y as f32) / CLUMP_SIZE,
+        1.0,
+    )
+    .x;
+
+    kind.get_density(biome, height) * (1.0 - clump as f64).clamp(0.0, 1.0)
+}
+
+fn sample(
+    kind: PropKind,
+    bounds: &Bounds,
+    surface: &TerrainSurface,
+    biome_map: &BiomeMap,
+    seed: u64,
+) -> Vec<Vector2f64> {
+    let settings = kind.get_settings();
+
+    deep_voronoi::sample_poisson_disk_by_density(
+        bounds,
+        settings.spacing_min,
+        settings.spacing_max,
+        |point| get_density(kind, point, surface, biome_map),
+        seed,
+    )
+}
+
+fn place(
+    kind: PropKind,
+    points: &[Vector2f64],
+    surface: &TerrainSurface,
+    biome_map: &BiomeMap,
+    seed: u64,
+) -> Vec<Transform> {
+    let settings = kind.get_settings();
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    points
+        .iter()
+        .filter_map(|point| {
+            // INFO: the density only drives the spacing, which still covers areas without any
+            if get_density(kind, *point, surface, biome_map) <= 0.0 {
+                return None;
+            }
+
+            let (position, normal) = surface.get_surface(point.x as f32, point.y as f32)?;
+            if normal.angle_between(Vec3::Y) > settings.slope_max {
+                return None;
+            }
+
+            let up = Vec3::Y.lerp(normal, settings.alignment).normalize();
+            let rotation = Quat::from_rotation_arc(Vec3::Y, up)
+                * Quat::from_rotation_y(rng.gen_range(0.0..std::f32::consts::TAU));
+            let scale = rng.gen_range(settings.scale_min..=settings.scale_max);
+
+            Some(Transform {
+                translation: position,
+                rotation,
+                scale: Vec3::splat(scale),
+            })
+        })
+        .collect()
+}
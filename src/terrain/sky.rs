@@ -1,4 +1,7 @@
-use std::time::Duration;
+use std::{
+    f32::consts::{PI, TAU},
+    time::Duration,
+};
 
 use bevy::{
     pbr::CascadeShadowConfigBuilder,
@@ -10,6 +13,11 @@ use bevy_atmosphere::{
     system_param::AtmosphereMut,
 };
 
+// INFO: length of one day/night cycle at scale 1.0
+const DAY_DURATION: Duration = Duration::from_secs(120);
+const SUN_ILLUMINANCE: f32 = 100000.0;
+const MOON_ILLUMINANCE: f32 = 400.0;
+
 #[derive(Default)]
 pub struct SkyPlugin {}
 
@@ -22,17 +30,131 @@ impl Plugin for SkyPlugin {
                 Duration::from_millis(50),
                 TimerMode::Repeating,
             )))
+            .insert_resource(WorldClock::default())
+            .add_event::<DaylightEvent>()
             .add_plugin(AtmospherePlugin)
             .add_system(setup.on_startup())
-            .add_system(daylight_cycle);
+            .add_system(update_world_clock)
+            .add_system(daylight_cycle.after(update_world_clock));
     }
 }
+
 #[derive(Component)]
 struct Sun;
 
+#[derive(Component)]
+struct Moon;
+
 #[derive(Resource)]
 struct CycleTimer(Timer);
 
+/// Light of the sun or moon as it reaches the sea level, the ocean attenuates it below.
+#[derive(Component)]
+pub struct CelestialLightComponent {
+    pub surface_color: Color,
+    pub surface_illuminance: f32,
+}
+
+/// Time of day as fraction of the cycle: 0.0 is midnight, 0.25 dawn, 0.5 noon and 0.75 dusk.
+#[derive(Resource)]
+pub struct WorldClock {
+    pub day_duration: Duration,
+    time_of_day: f32,
+    scale: f32,
+    paused: bool,
+    daylight: bool,
+}
+
+impl Default for WorldClock {
+    fn default() -> Self {
+        Self {
+            day_duration: DAY_DURATION,
+            time_of_day: 0.25,
+            scale: 1.0,
+            paused: false,
+            daylight: false,
+        }
+    }
+}
+
+impl WorldClock {
+    /// Advances the clock, returns the event if the sun crossed the horizon since the last tick.
+    pub fn tick(&mut self, delta: Duration) -> Option<DaylightEvent> {
+        if !self.paused {
+            let days = delta.as_secs_f32() * self.scale / self.day_duration.as_secs_f32();
+            self.time_of_day = (self.time_of_day + days).rem_euclid(1.0);
+        }
+
+        let is_day = self.is_day();
+        if self.daylight == is_day {
+            return None;
+        }
+
+        self.daylight = is_day;
+        Some(if is_day {
+            DaylightEvent::Dawn
+        } else {
+            DaylightEvent::Dusk
+        })
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn get_scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Speeds up the cycle by `scale`, negative values run it backwards.
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+    }
+
+    pub fn set_time_of_day(&mut self, time_of_day: f32) {
+        self.time_of_day = time_of_day.rem_euclid(1.0);
+    }
+
+    pub fn get_time_of_day(&self) -> f32 {
+        self.time_of_day
+    }
+
+    pub fn is_day(&self) -> bool {
+        self.get_sun_direction().y > 0.0
+    }
+
+    /// Angle of the sun above the eastern horizon, zero at dawn.
+    pub fn get_sun_angle(&self) -> f32 {
+        (self.time_of_day - 0.25) * TAU
+    }
+
+    /// Direction towards the sun, it rises in +z and sets in -z.
+    pub fn get_sun_direction(&self) -> Vec3 {
+        let angle = self.get_sun_angle();
+        Vec3::new(0.0, angle.sin(), angle.cos())
+    }
+
+    /// Direction towards the moon, always opposite of the sun.
+    pub fn get_moon_direction(&self) -> Vec3 {
+        -self.get_sun_direction()
+    }
+}
+
+/// Sent whenever the sun crosses the horizon.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DaylightEvent {
+    Dawn,
+    Dusk,
+}
+
 fn setup(mut commands: Commands) {
     commands
         .spawn(DirectionalLightBundle {
@@ -47,25 +169,145 @@ fn setup(mut commands: Commands) {
             .build(),
             ..default()
         })
-        .insert(Sun);
+        .insert((
+            Sun,
+            CelestialLightComponent {
+                surface_color: Color::WHITE,
+                surface_illuminance: 0.0,
+            },
+        ));
+
+    commands.spawn(DirectionalLightBundle::default()).insert((
+        Moon,
+        CelestialLightComponent {
+            surface_color: Color::rgb(0.75, 0.8, 1.0),
+            surface_illuminance: 0.0,
+        },
+    ));
+}
+
+fn update_world_clock(
+    time: Res<Time>,
+    mut clock: ResMut<WorldClock>,
+    mut daylight_events: EventWriter<DaylightEvent>,
+) {
+    if let Some(event) = clock.tick(time.delta()) {
+        daylight_events.send(event);
+    }
 }
 
 fn daylight_cycle(
     mut atmosphere: AtmosphereMut<Nishita>,
-    mut query: Query<(&mut Transform, &mut DirectionalLight), With<Sun>>,
+    mut sun_query: Query<(&mut Transform, &mut CelestialLightComponent), With<Sun>>,
+    mut moon_query: Query<
+        (&mut Transform, &mut CelestialLightComponent),
+        (With<Moon>, Without<Sun>),
+    >,
     mut timer: ResMut<CycleTimer>,
+    clock: Res<WorldClock>,
     time: Res<Time>,
 ) {
     timer.0.tick(time.delta());
 
     if timer.0.finished() {
-        // TODO: / 20.0 manipulates the duration: refactor into Duration of one day/night cycle
-        let t = time.elapsed_seconds_wrapped() / 20.0;
-        atmosphere.sun_position = Vec3::new(0., t.sin(), t.cos());
+        let angle = clock.get_sun_angle();
+        atmosphere.sun_position = clock.get_sun_direction();
+
+        if let Ok((mut light_trans, mut light)) = sun_query.get_single_mut() {
+            light_trans.rotation = Quat::from_rotation_x(-angle);
+            light.surface_illuminance =
+                clock.get_sun_direction().y.max(0.0).powf(2.0) * SUN_ILLUMINANCE;
+        }
 
-        if let Some((mut light_trans, mut directional)) = query.single_mut().into() {
-            light_trans.rotation = Quat::from_rotation_x(-t.sin().atan2(t.cos()));
-            directional.illuminance = t.sin().max(0.0).powf(2.0) * 100000.0;
+        if let Ok((mut light_trans, mut light)) = moon_query.get_single_mut() {
+            light_trans.rotation = Quat::from_rotation_x(-(angle + PI));
+            light.surface_illuminance =
+                clock.get_moon_direction().y.max(0.0).powf(2.0) * MOON_ILLUMINANCE;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-5;
+
+    fn seconds(seconds: f32) -> Duration {
+        Duration::from_secs_f32(seconds)
+    }
+
+    #[test]
+    fn tick_advances_by_day_fraction() {
+        let mut clock = WorldClock::default();
+        clock.tick(seconds(30.0));
+
+        assert!((clock.get_time_of_day() - 0.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn tick_wraps_around_midnight() {
+        let mut clock = WorldClock::default();
+        clock.set_time_of_day(0.9);
+        clock.tick(seconds(24.0));
+
+        assert!((clock.get_time_of_day() - 0.1).abs() < EPSILON);
+
+        clock.set_time_of_day(-0.25);
+        assert!((clock.get_time_of_day() - 0.75).abs() < EPSILON);
+    }
+
+    #[test]
+    fn negative_scale_runs_backwards() {
+        let mut clock = WorldClock::default();
+        clock.set_time_of_day(0.1);
+        clock.set_scale(-1.0);
+        clock.tick(seconds(24.0));
+
+        assert!((clock.get_time_of_day() - 0.9).abs() < EPSILON);
+    }
+
+    #[test]
+    fn paused_clock_keeps_time() {
+        let mut clock = WorldClock::default();
+        clock.pause();
+        clock.tick(seconds(30.0));
+
+        assert!(clock.is_paused());
+        assert!((clock.get_time_of_day() - 0.25).abs() < EPSILON);
+
+        clock.resume();
+        clock.tick(seconds(30.0));
+
+        assert!(!clock.is_paused());
+        assert!((clock.get_time_of_day() - 0.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn tick_reports_dawn_and_dusk_once() {
+        let mut clock = WorldClock::default();
+
+        assert_eq!(clock.tick(seconds(1.0)), Some(DaylightEvent::Dawn));
+        assert_eq!(clock.tick(seconds(1.0)), None);
+        assert_eq!(clock.tick(seconds(60.0)), Some(DaylightEvent::Dusk));
+        assert_eq!(clock.tick(seconds(1.0)), None);
+
+        // INFO: jumping across the horizon is reported on the next tick
+        clock.set_time_of_day(0.5);
+        clock.pause();
+        assert_eq!(clock.tick(seconds(1.0)), Some(DaylightEvent::Dawn));
+    }
+
+    #[test]
+    fn sun_and_moon_are_opposite() {
+        let mut clock = WorldClock::default();
+        clock.set_time_of_day(0.5);
+
+        assert!((clock.get_sun_direction() - Vec3::Y).length() < EPSILON);
+        assert!((clock.get_moon_direction() + Vec3::Y).length() < EPSILON);
+        assert!(clock.is_day());
+
+        clock.set_time_of_day(0.0);
+        assert!(!clock.is_day());
+    }
+}
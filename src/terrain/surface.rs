@@ -0,0 +1,128 @@
+use bevy::prelude::*;
+
+// INFO: side length of the cells triangles are bucketed into
+const CELL_SIZE: f32 = 8.0;
+
+/// Triangles of the terrain mesh bucketed into square cells, answering height and normal queries
+/// in world space on the actual simplified mesh instead of the height map.
+#[derive(Resource)]
+pub struct TerrainSurface {
+    translation: Vec3,
+    min: Vec2,
+    max: Vec2,
+    columns: usize,
+    rows: usize,
+    vertices: Vec<Vec3>,
+    indices: Vec<[u32; 3]>,
+    cells: Vec<Vec<usize>>,
+}
+
+impl TerrainSurface {
+    pub fn new(vertices: &[Vec3], indices: &[[u32; 3]], translation: Vec3) -> Self {
+        let (min, max) = vertices.iter().fold(
+            (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+            |(min, max), vertex| (min.min(vertex.xz()), max.max(vertex.xz())),
+        );
+
+        let columns = ((max.x - min.x) / CELL_SIZE).ceil().max(1.0) as usize;
+        let rows = ((max.y - min.y) / CELL_SIZE).ceil().max(1.0) as usize;
+
+        let mut surface = Self {
+            translation,
+            min,
+            max,
+            columns,
+            rows,
+            vertices: vertices.to_vec(),
+            indices: indices.to_vec(),
+            cells: vec![Vec::new(); columns * rows],
+        };
+
+        for (index, triangle) in indices.iter().enumerate() {
+            let points = triangle.map(|vertex| vertices[vertex as usize].xz());
+            let triangle_min = points[0].min(points[1]).min(points[2]);
+            let triangle_max = points[0].max(points[1]).max(points[2]);
+
+            let (column_min, row_min) = surface.get_cell(triangle_min);
+            let (column_max, row_max) = surface.get_cell(triangle_max);
+            for row in row_min..=row_max {
+                for column in column_min..=column_max {
+                    surface.cells[row * columns + column].push(index);
+                }
+            }
+        }
+
+        surface
+    }
+
+    /// Returns the world space rectangle covered by the terrain as minimum and maximum.
+    pub fn get_bounds(&self) -> (Vec2, Vec2) {
+        (
+            self.min + self.translation.xz(),
+            self.max + self.translation.xz(),
+        )
+    }
+
+    /// Converts a world position into the space of the terrain mesh.
+    pub fn to_local(&self, position: Vec3) -> Vec3 {
+        position - self.translation
+    }
+
+    /// Returns the world space point on the terrain below or above `x`, `z` and the upward
+    /// facing normal of its triangle.
+    pub fn get_surface(&self, x: f32, z: f32) -> Option<(Vec3, Vec3)> {
+        let point = Vec2::new(x, z) - self.translation.xz();
+        if point.cmplt(self.min).any() || point.cmpgt(self.max).any() {
+            return None;
+        }
+
+        let (column, row) = self.get_cell(point);
+        for index in self.cells[row * self.columns + column].iter() {
+            let [a, b, c] = self.indices[*index].map(|vertex| self.vertices[vertex as usize]);
+
+            if let Some([u, v, w]) = get_barycentric(point, a.xz(), b.xz(), c.xz()) {
+                let height = a.y * u + b.y * v + c.y * w;
+
+                let mut normal = (b - a).cross(c - a).normalize();
+                if normal.y < 0.0 {
+                    normal = -normal;
+                }
+
+                return Some((
+                    Vec3::new(point.x, height, point.y) + self.translation,
+                    normal,
+                ));
+            }
+        }
+
+        None
+    }
+
+    fn get_cell(&self, point: Vec2) -> (usize, usize) {
+        let cell = ((point - self.min) / CELL_SIZE).max(Vec2::ZERO);
+
+        (
+            (cell.x as usize).min(self.columns - 1),
+            (cell.y as usize).min(self.rows - 1),
+        )
+    }
+}
+
+fn get_barycentric(point: Vec2, a: Vec2, b: Vec2, c: Vec2) -> Option<[f32; 3]> {
+    let denominator = (b - a).perp_dot(c - a);
+    if denominator.abs() <= f32::EPSILON {
+        return None;
+    }
+
+    let v = (point - a).perp_dot(c - a) / denominator;
+    let w = (b - a).perp_dot(point - a) / denominator;
+    let u = 1.0 - v - w;
+
+    // INFO: small tolerance so points on shared edges are never missed
+    let tolerance = -1e-5;
+    if u >= tolerance && v >= tolerance && w >= tolerance {
+        Some([u, v, w])
+    } else {
+        None
+    }
+}